@@ -1,8 +1,106 @@
+mod compaction;
+mod conversion;
 mod cut;
+mod export;
+mod flow;
+mod global;
+mod gomory_hu;
 mod important_cut;
+mod labeled;
+mod memo;
+mod minimum_cut;
+mod multiway;
 mod naive;
 mod path_residual;
+mod render;
+mod solver;
+mod verify;
 
+pub use conversion::from_graph_map;
+pub use conversion::to_directed;
+pub use conversion::to_undirected;
+pub use conversion::un_graph_from_adjacency;
+pub use conversion::AdjacencyError;
+#[cfg(feature = "serde")]
+pub use conversion::{un_graph_from_adjacency_json, ParseError};
+pub use cut::edge_cut_membership;
+pub use cut::expand_cut;
 pub use cut::Cut;
 pub use cut::ImportantCut;
+pub use export::cut_to_csv;
+pub use export::cut_to_matrices;
+pub use export::important_cuts_to_csv;
+#[cfg(feature = "serde")]
+pub use export::important_cuts_to_json;
+pub use export::important_cuts_to_text;
+pub use export::write_important_cuts_to_path;
+pub use export::OutputFormat;
+pub use global::global_min_cut;
+pub use global::karger_min_cut;
+pub use gomory_hu::gomory_hu_tree;
+pub use important_cut::important_cut_sizes;
 pub use important_cut::important_cuts;
+pub use important_cut::important_cuts_by_size;
+pub use important_cut::important_cuts_full;
+pub use important_cut::important_cuts_into_channel;
+pub use important_cut::important_cuts_iter;
+pub use important_cut::important_cuts_with_capacities;
+pub use important_cut::important_cuts_with_excluded_edges;
+pub use important_cut::important_cuts_with_forced_edges;
+pub use important_cut::important_cuts_with_max_depth;
+pub use important_cut::important_cuts_with_node_filter;
+pub use important_cut::important_cuts_with_paths;
+pub use important_cut::important_cuts_with_payloads;
+pub use important_cut::important_cuts_with_sides;
+pub use important_cut::important_cuts_with_stats;
+pub use important_cut::min_cost_important_cut;
+pub use important_cut::smallest_important_cut;
+pub use important_cut::try_important_cuts;
+pub use important_cut::try_important_cuts_iter;
+pub use important_cut::try_important_cuts_with_capacities;
+pub use important_cut::try_important_cuts_with_excluded_edges;
+pub use important_cut::try_important_cuts_with_forced_edges;
+pub use important_cut::try_important_cuts_with_node_filter;
+pub use important_cut::try_min_cost_important_cut;
+pub use important_cut::try_smallest_important_cut;
+pub use important_cut::CutStats;
+pub use important_cut::ImportantCutsIter;
+pub use labeled::LabeledGraph;
+pub use labeled::LabeledGraphBuilder;
+pub use memo::MemoStats;
+pub use minimum_cut::edge_connectivity;
+pub use minimum_cut::explained_minimum_cut;
+pub use minimum_cut::extreme_minimum_cuts;
+pub use minimum_cut::max_flow_between_sets;
+pub use minimum_cut::max_flow_min_cut;
+pub use minimum_cut::max_flow_per_edge;
+pub use minimum_cut::minimum_cardinality_cuts;
+pub use minimum_cut::minimum_cut;
+pub use minimum_cut::minimum_cut_closest_to_source;
+pub use minimum_cut::minimum_cut_with_capacities;
+pub use minimum_cut::minimum_weight_cut;
+pub use minimum_cut::try_explained_minimum_cut;
+pub use minimum_cut::try_extreme_minimum_cuts;
+pub use minimum_cut::try_max_flow_between_sets;
+pub use minimum_cut::try_minimum_cardinality_cuts;
+pub use minimum_cut::try_minimum_cut;
+pub use minimum_cut::try_minimum_cut_closest_to_source;
+pub use minimum_cut::try_minimum_weight_cut;
+pub use minimum_cut::ExplainedCut;
+pub use multiway::multiway_cut;
+pub use naive::filter_important_cuts;
+pub use naive::generate_cuts;
+pub use naive::generate_cuts_for_sets;
+pub use path_residual::augmenting_paths;
+pub use path_residual::contract_vertices;
+pub use path_residual::residual_graph;
+pub use path_residual::try_augmenting_paths;
+pub use path_residual::IndexMapping;
+pub use path_residual::Path;
+pub use path_residual::ResidualGraph;
+pub use path_residual::UnGraph;
+pub use render::cut_to_dot;
+pub use render::render_ascii;
+pub use solver::CutSolver;
+pub use verify::is_important_cut;
+pub use verify::verify_cut;
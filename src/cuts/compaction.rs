@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+
+use crate::cuts::cut::Cut;
+use crate::cuts::path_residual::{IndexMapping, UnGraph};
+
+/// Maps the vertex and edge indices of an input graph to contiguous indices `0..n` and `0..m`.
+///
+/// The algorithms work on an [`UnGraph`] whose indices are contiguous, which is not the case for
+/// e.g. a `StableGraph` that has had nodes or edges removed. Only vertices that are endpoints of
+/// an edge or that were explicitly added are compacted, as other vertices cannot be part of a cut.
+#[derive(Debug)]
+pub(crate) struct Compaction {
+    vertex_to_compact: HashMap<usize, usize>,
+    compact_to_vertex: Vec<usize>,
+    compact_to_edge: Vec<usize>,
+}
+
+impl Compaction {
+    /// Builds the compacted graph of the given graph along with the mapping. The given vertices
+    /// are added to the compacted graph even if they are not the endpoint of any edge.
+    pub(crate) fn new<G>(graph: G, vertices: &[usize]) -> (UnGraph, Self)
+    where
+        G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    {
+        let mut compaction = Self {
+            vertex_to_compact: HashMap::new(),
+            compact_to_vertex: vec![],
+            compact_to_edge: vec![],
+        };
+
+        let mut edges = vec![];
+        for edge in graph.edge_references() {
+            let source = compaction.add_vertex(NodeIndexable::to_index(&graph, edge.source()));
+            let target = compaction.add_vertex(NodeIndexable::to_index(&graph, edge.target()));
+            edges.push((source, target));
+            compaction
+                .compact_to_edge
+                .push(EdgeIndexable::to_index(&graph, edge.id()));
+        }
+        for &vertex in vertices {
+            compaction.add_vertex(vertex);
+        }
+
+        let mut compacted_graph =
+            UnGraph::with_capacity(compaction.compact_to_vertex.len(), edges.len());
+        for _ in 0..compaction.compact_to_vertex.len() {
+            compacted_graph.add_node(());
+        }
+        for (source, target) in edges {
+            compacted_graph.add_edge(NodeIndex::new(source), NodeIndex::new(target), ());
+        }
+
+        (compacted_graph, compaction)
+    }
+
+    fn add_vertex(&mut self, vertex: usize) -> usize {
+        let next_index = self.compact_to_vertex.len();
+        let compact = *self.vertex_to_compact.entry(vertex).or_insert(next_index);
+        if compact == next_index {
+            self.compact_to_vertex.push(vertex);
+        }
+        compact
+    }
+
+    /// Compact indices of the given vertices of the input graph. Panics if a vertex was not
+    /// compacted.
+    pub(crate) fn compact_vertices(&self, vertices: &[usize]) -> Vec<usize> {
+        vertices
+            .iter()
+            .map(|vertex| match self.vertex_to_compact.get(vertex) {
+                None => panic!("Vertex {} is not in the compacted graph", vertex),
+                Some(&compact) => compact,
+            })
+            .collect()
+    }
+
+    /// Compact indices of the given edges of the input graph, or `None` if one of them does not
+    /// exist.
+    pub(crate) fn compact_edges(&self, edges: &[usize]) -> Option<Vec<usize>> {
+        edges
+            .iter()
+            .map(|edge| {
+                self.compact_to_edge
+                    .iter()
+                    .position(|original| original == edge)
+            })
+            .collect()
+    }
+
+    pub(crate) fn original_vertices(&self, compact_vertices: &[usize]) -> Vec<usize> {
+        compact_vertices
+            .iter()
+            .map(|&compact| self.compact_to_vertex[compact])
+            .collect()
+    }
+
+    pub(crate) fn original_edges(&self, compact_edges: &[usize]) -> Vec<usize> {
+        compact_edges
+            .iter()
+            .map(|&compact| self.compact_to_edge[compact])
+            .collect()
+    }
+
+    /// Maps the values of an index mapping of the compacted graph to the indices of the input
+    /// graph, so that it maps contracted indices to the indices of the input graph.
+    pub(crate) fn original_index_mapping(&self, index_mapping: IndexMapping) -> IndexMapping {
+        IndexMapping::from(
+            index_mapping
+                .vertex_map()
+                .iter()
+                .map(|(&contracted, vertices)| (contracted, self.original_vertices(vertices)))
+                .collect(),
+            index_mapping
+                .edge_map()
+                .iter()
+                .map(|(&contracted, edges)| (contracted, self.original_edges(edges)))
+                .collect(),
+        )
+    }
+
+    /// Maps a cut of the compacted graph back to the indices of the input graph.
+    pub(crate) fn original_cut(&self, cut: Cut) -> Cut {
+        Cut::new(
+            self.original_vertices(&cut.source_set),
+            self.original_vertices(&cut.destination_set),
+            self.original_edges(&cut.cut_edge_set),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::stable_graph::{NodeIndex, StableUnGraph};
+
+    use crate::cuts::compaction::Compaction;
+
+    #[test]
+    fn compaction_skips_removed_indices() {
+        let mut graph = StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (0, 3)]);
+        graph.remove_node(NodeIndex::new(1));
+
+        let (compacted_graph, compaction) = Compaction::new(&graph, &[]);
+
+        assert_eq!(3, compacted_graph.node_count());
+        assert_eq!(2, compacted_graph.edge_count());
+        assert_eq!(vec![0, 1, 2], compaction.compact_vertices(&[2, 3, 0]));
+        assert_eq!(vec![2, 3, 0], compaction.original_vertices(&[0, 1, 2]));
+        assert_eq!(vec![2, 3], compaction.original_edges(&[0, 1]));
+    }
+}
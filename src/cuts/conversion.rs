@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+#[cfg(feature = "serde")]
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+
+use petgraph::graph::{IndexType, NodeIndex};
+use petgraph::graphmap::{GraphMap, NodeTrait};
+use petgraph::visit::EdgeRef;
+use petgraph::{Directed, EdgeType, Graph, Undirected};
+
+use crate::cuts::path_residual::UnGraph;
+
+/// Builds a directed graph with the given number of nodes in which every edge is replaced by a
+/// pair of arcs pointing in opposite directions. The arcs of edge `i` get the indices `2i` and
+/// `2i + 1`.
+pub(crate) fn arc_pairs<Ix: IndexType>(
+    node_count: usize,
+    edges: impl IntoIterator<Item = (usize, usize)>,
+) -> Graph<(), (), Directed, Ix> {
+    let mut directed = Graph::default();
+    for _ in 0..node_count {
+        directed.add_node(());
+    }
+    for (source, target) in edges {
+        let (source, target) = (NodeIndex::new(source), NodeIndex::new(target));
+        directed.add_edge(source, target, ());
+        directed.add_edge(target, source, ());
+    }
+    directed
+}
+
+/// Converts an undirected graph into a directed one in which each edge becomes two arcs, one in
+/// each direction. The vertex indices stay the same, and the arcs of edge `i` get the indices `2i`
+/// and `2i + 1`.
+pub fn to_directed<Ix: IndexType>(
+    graph: &Graph<(), (), Undirected, Ix>,
+) -> Graph<(), (), Directed, Ix> {
+    arc_pairs(
+        graph.node_count(),
+        graph
+            .edge_references()
+            .map(|edge| (edge.source().index(), edge.target().index())),
+    )
+}
+
+/// Converts a directed graph into an undirected one by merging each pair of opposite arcs into a
+/// single edge. The vertex indices stay the same, and the edges are in the order of the first arc
+/// of each pair.
+///
+/// An arc whose opposite arc does not exist becomes an edge of its own, as does every arc that is
+/// left over when there are more arcs in one direction than in the other. Hence converting a
+/// graph with [`to_directed`] and back yields the original graph.
+pub fn to_undirected<Ix: IndexType>(
+    graph: &Graph<(), (), Directed, Ix>,
+) -> Graph<(), (), Undirected, Ix> {
+    let mut undirected = Graph::with_capacity(graph.node_count(), graph.edge_count() / 2);
+    for _ in 0..graph.node_count() {
+        undirected.add_node(());
+    }
+
+    // number of arcs in each direction that have not been merged with an opposite arc yet
+    let mut unmatched_arcs = HashMap::<(usize, usize), usize>::new();
+    for arc in graph.edge_references() {
+        let (source, target) = (arc.source().index(), arc.target().index());
+        match unmatched_arcs.get_mut(&(target, source)) {
+            Some(count) if *count > 0 => *count -= 1,
+            _ => {
+                *unmatched_arcs.entry((source, target)).or_default() += 1;
+                undirected.add_edge(arc.source(), arc.target(), ());
+            }
+        }
+    }
+    undirected
+}
+
+/// Reasons why [`un_graph_from_adjacency`] can reject a matrix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AdjacencyError {
+    /// The row does not have as many entries as the matrix has rows
+    NotSquare { row: usize, length: usize },
+}
+
+impl Display for AdjacencyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AdjacencyError::NotSquare { row, length } => {
+                write!(
+                    f,
+                    "Row {} of the adjacency matrix has length {}",
+                    row, length
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for AdjacencyError {}
+
+/// Builds an undirected graph from a symmetric adjacency matrix, with a vertex for each row and an
+/// edge for each nonzero entry above the diagonal. The entries below and on the diagonal are
+/// ignored, as are the values of the entries other than being nonzero.
+pub fn un_graph_from_adjacency(matrix: &[Vec<u8>]) -> Result<UnGraph, AdjacencyError> {
+    if let Some((row, entries)) = matrix
+        .iter()
+        .enumerate()
+        .find(|(_, entries)| entries.len() != matrix.len())
+    {
+        return Err(AdjacencyError::NotSquare {
+            row,
+            length: entries.len(),
+        });
+    }
+
+    let mut graph = UnGraph::with_capacity(matrix.len(), 0);
+    for _ in 0..matrix.len() {
+        graph.add_node(());
+    }
+    for (i, entries) in matrix.iter().enumerate() {
+        for (j, &entry) in entries.iter().enumerate().skip(i + 1) {
+            if entry != 0 {
+                graph.add_edge(NodeIndex::new(i), NodeIndex::new(j), ());
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Converts a [`GraphMap`] into an undirected graph, together with the index of the vertex of each
+/// node key, so that the vertices of a cut can be translated back to their keys. The vertices are
+/// in the order of the nodes of the map, and the edges of a directed map become undirected.
+pub fn from_graph_map<N, E, Ty>(graph_map: &GraphMap<N, E, Ty>) -> (UnGraph, HashMap<N, usize>)
+where
+    N: NodeTrait,
+    Ty: EdgeType,
+{
+    let indices = graph_map
+        .nodes()
+        .enumerate()
+        .map(|(index, node)| (node, index))
+        .collect::<HashMap<_, _>>();
+
+    let mut graph = UnGraph::with_capacity(indices.len(), graph_map.edge_count());
+    for _ in 0..indices.len() {
+        graph.add_node(());
+    }
+    for (source, target, _) in graph_map.all_edges() {
+        graph.add_edge(
+            NodeIndex::new(indices[&source]),
+            NodeIndex::new(indices[&target]),
+            (),
+        );
+    }
+    (graph, indices)
+}
+
+/// Reasons why [`un_graph_from_adjacency_json`] can reject its input.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input is not a JSON object mapping vertices to lists of vertices
+    Json(String),
+    /// The key of the object is not a vertex index
+    InvalidVertex(String),
+}
+
+#[cfg(feature = "serde")]
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Json(message) => write!(f, "Invalid adjacency list: {}", message),
+            ParseError::InvalidVertex(key) => write!(f, "Key {:?} is not a vertex index", key),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for ParseError {}
+
+/// Builds an undirected graph from a JSON object mapping each vertex to the list of its neighbors,
+/// e.g. `{"0": [1, 2], "1": [0]}`. An edge listed by both of its endpoints becomes a single edge.
+/// The graph has a vertex for every index up to the largest one that occurs, and its edges are
+/// ordered by the vertex that lists them first.
+#[cfg(feature = "serde")]
+pub fn un_graph_from_adjacency_json(json: &str) -> Result<UnGraph, ParseError> {
+    let lists = serde_json::from_str::<HashMap<String, Vec<usize>>>(json)
+        .map_err(|error| ParseError::Json(error.to_string()))?;
+    let mut lists = lists
+        .into_iter()
+        .map(|(key, neighbors)| match key.parse::<usize>() {
+            Ok(vertex) => Ok((vertex, neighbors)),
+            Err(_) => Err(ParseError::InvalidVertex(key)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    lists.sort();
+
+    let node_count = lists
+        .iter()
+        .flat_map(|(vertex, neighbors)| neighbors.iter().chain([vertex]))
+        .max()
+        .map_or(0, |&vertex| vertex + 1);
+    let mut graph = UnGraph::with_capacity(node_count, 0);
+    for _ in 0..node_count {
+        graph.add_node(());
+    }
+
+    let mut added = HashSet::new();
+    for (vertex, neighbors) in lists {
+        for neighbor in neighbors {
+            if added.insert((vertex.min(neighbor), vertex.max(neighbor))) {
+                graph.add_edge(NodeIndex::new(vertex), NodeIndex::new(neighbor), ());
+            }
+        }
+    }
+    Ok(graph)
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::NodeIndex;
+    use petgraph::graphmap::UnGraphMap;
+    use petgraph::visit::EdgeRef;
+
+    use crate::cuts::conversion::{
+        from_graph_map, to_directed, to_undirected, un_graph_from_adjacency, AdjacencyError,
+    };
+    #[cfg(feature = "serde")]
+    use crate::cuts::conversion::{un_graph_from_adjacency_json, ParseError};
+    use crate::cuts::minimum_cut::minimum_cut;
+    use crate::cuts::path_residual::{ResidualGraph, UnGraph};
+
+    fn edge_list<E>(edges: impl Iterator<Item = E>) -> Vec<(usize, usize)>
+    where
+        E: EdgeRef<NodeId = NodeIndex<usize>>,
+    {
+        edges
+            .map(|edge| (edge.source().index(), edge.target().index()))
+            .collect()
+    }
+
+    #[test]
+    fn undirected_round_trip() {
+        let mut graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3), (2, 3), (1, 2)]);
+        graph.add_node(());
+
+        let directed = to_directed(&graph);
+        assert_eq!(5, directed.node_count());
+        assert_eq!(10, directed.edge_count());
+        assert_eq!(
+            vec![(0, 1), (1, 0)],
+            edge_list(directed.edge_references())[..2]
+        );
+
+        let undirected = to_undirected(&directed);
+        assert_eq!(graph.node_count(), undirected.node_count());
+        assert_eq!(
+            edge_list(graph.edge_references()),
+            edge_list(undirected.edge_references())
+        );
+    }
+
+    #[test]
+    fn asymmetric_arcs_become_edges() {
+        let directed = ResidualGraph::from_edges([(0, 1), (1, 2), (1, 0), (0, 1), (2, 0)]);
+
+        let undirected = to_undirected(&directed);
+        assert_eq!(
+            vec![(0, 1), (1, 2), (0, 1), (2, 0)],
+            edge_list(undirected.edge_references())
+        );
+    }
+
+    #[test]
+    fn graph_from_adjacency_matrix() {
+        // 0 - 1 - 2 - 3 with the chord (0, 2)
+        let matrix = vec![
+            vec![0, 1, 1, 0],
+            vec![1, 0, 1, 0],
+            vec![1, 1, 0, 1],
+            vec![0, 0, 1, 0],
+        ];
+
+        let graph = un_graph_from_adjacency(&matrix).unwrap();
+        assert_eq!(4, graph.node_count());
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 2), (2, 3)],
+            edge_list(graph.edge_references())
+        );
+
+        assert_eq!(
+            AdjacencyError::NotSquare { row: 1, length: 3 },
+            un_graph_from_adjacency(&[vec![0, 1], vec![1, 0, 0]]).unwrap_err()
+        );
+    }
+
+    #[test]
+    fn graph_from_graph_map() {
+        let graph_map =
+            UnGraphMap::<char, ()>::from_edges([('s', 'a'), ('a', 'b'), ('s', 'b'), ('b', 't')]);
+
+        let (graph, indices) = from_graph_map(&graph_map);
+        assert_eq!(4, graph.node_count());
+        assert_eq!(4, graph.edge_count());
+
+        // the key of a vertex in a cut can be found through the mapping
+        let cut = minimum_cut(&graph, vec![indices[&'s']], vec![indices[&'t']]).unwrap();
+        let keys = indices
+            .iter()
+            .filter(|(_, index)| cut.destination_set.contains(index))
+            .map(|(&key, _)| key)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!['t'], keys);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn graph_from_adjacency_json() {
+        let json = r#"{"0": [1, 2], "1": [0, 3], "2": [0], "3": [1], "4": []}"#;
+
+        let graph = un_graph_from_adjacency_json(json).unwrap();
+        assert_eq!(5, graph.node_count());
+        assert_eq!(3, graph.edge_count());
+        assert_eq!(
+            vec![(0, 1), (0, 2), (1, 3)],
+            edge_list(graph.edge_references())
+        );
+
+        assert_eq!(
+            ParseError::InvalidVertex("a".to_string()),
+            un_graph_from_adjacency_json(r#"{"a": [0]}"#).unwrap_err()
+        );
+        assert!(matches!(
+            un_graph_from_adjacency_json("[0, 1]"),
+            Err(ParseError::Json(_))
+        ));
+    }
+}
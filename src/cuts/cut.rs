@@ -1,13 +1,21 @@
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
 
+use fixedbitset::FixedBitSet;
 use itertools::Itertools;
 use petgraph::graph::EdgeIndex;
 use petgraph::prelude::Bfs;
-use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable, Reversed};
+#[cfg(not(feature = "deterministic"))]
 use rand::prelude::SliceRandom;
+#[cfg(not(feature = "deterministic"))]
 use rand::thread_rng;
 
+use crate::cuts::export::important_cuts_to_text;
 use crate::cuts::path_residual::{IndexMapping, Path, ResidualGraph, UnGraph};
+use crate::cuts::verify::reachable_vertices;
+use crate::{Error, MappingKind};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Cut {
@@ -32,46 +40,188 @@ impl Cut {
         }
     }
 
+    /// Same as [`Cut::new`], but checks that no vertex is on both sides of the cut. Duplicate cut
+    /// edges are removed, so that the size of the cut is the number of distinct edges.
+    pub fn try_new(
+        source_set: Vec<usize>,
+        destination_set: Vec<usize>,
+        cut_edge_set: Vec<usize>,
+    ) -> Result<Self, Error> {
+        let source_vertices = source_set.iter().collect::<HashSet<_>>();
+        if let Some(&vertex) = destination_set
+            .iter()
+            .find(|vertex| source_vertices.contains(vertex))
+        {
+            return Err(Error::OverlappingSides(vertex));
+        }
+        Ok(Self::new(
+            source_set,
+            destination_set,
+            cut_edge_set.into_iter().unique().collect(),
+        ))
+    }
+
+    /// Total weight of the cut edges, where `weight_of` gives the weight of an edge by its index.
+    /// Panics if the sum overflows, see [`Cut::try_weight`].
+    pub fn weight<F: Fn(usize) -> u64>(&self, weight_of: F) -> u64 {
+        self.try_weight(weight_of)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Same as [`Cut::weight`], but returns an error instead of panicking if the sum overflows.
+    pub fn try_weight<F: Fn(usize) -> u64>(&self, weight_of: F) -> Result<u64, Error> {
+        total_weight(&self.cut_edge_set, weight_of)
+    }
+
+    /// Whether the cut separates the two vertices, i.e. `source` is on the source side and
+    /// `destination` is on the destination side.
+    pub fn separates(&self, source: usize, destination: usize) -> bool {
+        self.source_set.contains(&source) && self.destination_set.contains(&destination)
+    }
+
+    /// The cut whose source side is the union of the source sides of both cuts. Its destination
+    /// side is made of the other vertices of the two cuts, and its edges are the edges of the
+    /// graph crossing between the sides. The sides and the edges are sorted.
+    pub fn union_source<G>(&self, other: &Cut, graph: G) -> Cut
+    where
+        G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    {
+        let source_set = self
+            .source_set
+            .iter()
+            .chain(&other.source_set)
+            .copied()
+            .collect::<BTreeSet<_>>();
+        let destination_set = self
+            .destination_set
+            .iter()
+            .chain(&other.destination_set)
+            .copied()
+            .filter(|vertex| !source_set.contains(vertex))
+            .collect::<BTreeSet<_>>();
+        let cut_edge_set = graph
+            .edge_references()
+            .filter(|edge| {
+                let source = NodeIndexable::to_index(&graph, edge.source());
+                let target = NodeIndexable::to_index(&graph, edge.target());
+                (source_set.contains(&source) && destination_set.contains(&target))
+                    || (source_set.contains(&target) && destination_set.contains(&source))
+            })
+            .map(|edge| EdgeIndexable::to_index(&graph, edge.id()))
+            .sorted()
+            .collect();
+        Cut::new(
+            source_set.into_iter().collect(),
+            destination_set.into_iter().collect(),
+            cut_edge_set,
+        )
+    }
+
+    /// Endpoints of each cut edge in the given graph, in the order of the cut edges. Panics if an
+    /// edge does not exist in the graph.
+    pub fn cut_edge_endpoints<G>(&self, graph: G) -> Vec<(usize, usize)>
+    where
+        G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    {
+        let endpoints = graph
+            .edge_references()
+            .map(|edge| {
+                (
+                    EdgeIndexable::to_index(&graph, edge.id()),
+                    (
+                        NodeIndexable::to_index(&graph, edge.source()),
+                        NodeIndexable::to_index(&graph, edge.target()),
+                    ),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        self.cut_edge_set
+            .iter()
+            .map(|edge| match endpoints.get(edge) {
+                None => panic!("Edge {} does not exist in graph.", edge),
+                Some(&endpoints) => endpoints,
+            })
+            .collect()
+    }
+
     /// Pick arbitrary edge from cut. Returns a tuple of the edge index and the node index that lies
-    /// in the destination set. Panics if edge does not exist, is not found or doesn't have
-    /// endpoints in the source and destination sets.
-    pub fn arbitrary_edge(&self, graph: &UnGraph) -> (usize, usize) {
-        match self.cut_edge_set.choose(&mut thread_rng()) {
-            None => panic!("Trying to get arbitrary edge from empty cut."),
-            Some(&edge) => match graph.edge_endpoints(EdgeIndex::from(edge)) {
-                None => panic!("Edge does not exist in graph."),
-                Some((node_a, node_b)) => {
-                    let node_a_index = NodeIndexable::to_index(&graph, node_a);
-                    let node_b_index = NodeIndexable::to_index(&graph, node_b);
-                    if self.source_set.contains(&node_a_index)
-                        && self.destination_set.contains(&node_b_index)
-                    {
-                        (edge, node_b_index)
-                    } else if self.source_set.contains(&node_b_index)
-                        && self.destination_set.contains(&node_a_index)
-                    {
-                        (edge, node_a_index)
-                    } else {
-                        panic!("Picked edge does not have one endpoint in source set and one in destination set");
-                    }
+    /// in the destination set. Fails if the cut is empty, or if the edge does not exist or doesn't
+    /// have endpoints in the source and destination sets. With the `deterministic` feature, the
+    /// edge with the smallest destination endpoint and then the smallest source endpoint is picked
+    /// instead, so that repeated searches branch in the same way.
+    pub fn arbitrary_edge(&self, graph: &UnGraph) -> Result<(usize, usize), Error> {
+        #[cfg(feature = "deterministic")]
+        {
+            let mut picked = None;
+            for &edge in &self.cut_edge_set {
+                let (source_vertex, destination_vertex) = self.crossing_endpoints(graph, edge)?;
+                let key = (destination_vertex, source_vertex, edge);
+                if picked.is_none_or(|picked| key < picked) {
+                    picked = Some(key);
                 }
-            },
+            }
+            picked
+                .map(|(destination_vertex, _, edge)| (edge, destination_vertex))
+                .ok_or(Error::EmptyCut)
+        }
+        #[cfg(not(feature = "deterministic"))]
+        {
+            let &edge = self
+                .cut_edge_set
+                .choose(&mut thread_rng())
+                .ok_or(Error::EmptyCut)?;
+            let (_, destination_vertex) = self.crossing_endpoints(graph, edge)?;
+            Ok((edge, destination_vertex))
+        }
+    }
+
+    /// Endpoints of the edge on the source side and on the destination side of the cut. Fails if
+    /// the edge does not exist or does not cross the cut.
+    fn crossing_endpoints(&self, graph: &UnGraph, edge: usize) -> Result<(usize, usize), Error> {
+        let (node_a, node_b) = graph
+            .edge_endpoints(EdgeIndex::from(edge))
+            .ok_or(Error::IllegalEndpoint(edge))?;
+        let node_a_index = NodeIndexable::to_index(&graph, node_a);
+        let node_b_index = NodeIndexable::to_index(&graph, node_b);
+        if self.source_set.contains(&node_a_index) && self.destination_set.contains(&node_b_index) {
+            Ok((node_a_index, node_b_index))
+        } else if self.source_set.contains(&node_b_index)
+            && self.destination_set.contains(&node_a_index)
+        {
+            Ok((node_b_index, node_a_index))
+        } else {
+            Err(Error::IllegalEndpoint(edge))
         }
     }
 }
 
-#[derive(Debug)]
+/// An important cut given by its edges, which are kept sorted and deduplicated, so that two cuts
+/// are equal if they consist of the same edges.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ImportantCut {
-    pub edge_indices: Vec<usize>,
+    edge_indices: Vec<usize>,
 }
 
 impl ImportantCut {
+    /// Creates an important cut of the given edges, which are sorted and deduplicated so that
+    /// equal cuts have equal edge vectors.
     pub fn from(edge_indices: Vec<usize>) -> Self {
         Self {
-            edge_indices: edge_indices.into_iter().unique().collect(),
+            edge_indices: edge_indices.into_iter().unique().sorted().collect(),
         }
     }
 
+    /// The edges of the cut, sorted.
+    pub fn edge_indices(&self) -> &[usize] {
+        &self.edge_indices
+    }
+
+    /// Takes the sorted edges out of the cut.
+    pub fn into_edge_indices(self) -> Vec<usize> {
+        self.edge_indices
+    }
+
     #[allow(dead_code)]
     pub fn vertex_pairs<G>(&self, graph: G) -> Vec<(usize, usize)>
     where
@@ -95,9 +245,72 @@ impl ImportantCut {
             .collect()
     }
 
+    /// Vertices that can be reached from the source set once the edges of the cut are removed,
+    /// sorted.
+    pub fn source_side<G>(&self, graph: G, source_set: &[usize]) -> Vec<usize>
+    where
+        G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    {
+        reachable_vertices(graph, source_set, |edge| !self.edge_indices.contains(&edge))
+            .into_iter()
+            .sorted()
+            .collect()
+    }
+
+    /// Vertices incident to an edge of the graph that are not on the source side of the cut, see
+    /// [`ImportantCut::source_side`], sorted.
+    pub fn destination_side<G>(&self, graph: G, source_set: &[usize]) -> Vec<usize>
+    where
+        G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    {
+        let source_side = self.source_side(graph, source_set);
+        graph
+            .edge_references()
+            .flat_map(|edge| {
+                [
+                    NodeIndexable::to_index(&graph, edge.source()),
+                    NodeIndexable::to_index(&graph, edge.target()),
+                ]
+            })
+            .filter(|vertex| source_side.binary_search(vertex).is_err())
+            .unique()
+            .sorted()
+            .collect()
+    }
+
+    /// Total weight of the edges of the cut, where `weight_of` gives the weight of an edge by its
+    /// index. Returns an error if the sum overflows.
+    pub fn try_weight<F: Fn(usize) -> u64>(&self, weight_of: F) -> Result<u64, Error> {
+        total_weight(&self.edge_indices, weight_of)
+    }
+
+    /// The edges of the cut as a bitset over the edge indices below `edge_count`, so that cuts can
+    /// be intersected and merged with `&` and `|`. Panics if an edge of the cut is not below
+    /// `edge_count`.
+    pub fn edge_bitset(&self, edge_count: usize) -> FixedBitSet {
+        let mut bitset = FixedBitSet::with_capacity(edge_count);
+        for &edge in &self.edge_indices {
+            bitset.insert(edge);
+        }
+        bitset
+    }
+
+    /// Edges that are in exactly one of the two cuts, sorted.
+    pub fn symmetric_difference(&self, other: &ImportantCut) -> Vec<usize> {
+        let edges = self.edge_indices.iter().collect::<HashSet<_>>();
+        let other_edges = other.edge_indices.iter().collect::<HashSet<_>>();
+        edges
+            .symmetric_difference(&other_edges)
+            .map(|&&edge| edge)
+            .sorted()
+            .collect()
+    }
+
     #[allow(dead_code)]
     pub fn vec_edge_indices(cuts: Vec<ImportantCut>) -> Vec<Vec<usize>> {
-        cuts.iter().map(|ic| ic.edge_indices.clone()).collect()
+        cuts.into_iter()
+            .map(ImportantCut::into_edge_indices)
+            .collect()
     }
 
     pub fn vec_vertex_indices<G>(graph: G, cuts: Vec<ImportantCut>) -> Vec<Vec<(usize, usize)>>
@@ -105,44 +318,62 @@ impl ImportantCut {
         G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
     {
         cuts.iter()
-            .map(|ic| ic.vertex_pairs(&graph))
+            .map(|ic| ic.vertex_pairs(graph))
             .unique()
             .collect()
     }
 
     pub fn print_important_cuts<G>(graph: G, cuts: Vec<ImportantCut>)
-        where
-            G: NodeIndexable + EdgeIndexable + IntoEdgeReferences, {
-        println!("Important cuts:");
-        for ic_indices in ImportantCut::vec_vertex_indices(&graph, cuts) {
-            println!("- {:?}", ic_indices);
-        }
+    where
+        G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    {
+        print!("{}", important_cuts_to_text(graph, &cuts));
     }
 }
 
-fn generate_minimum_cut_closest_to_destination(
-    paths: &Vec<Path>,
-    residual_graph_reverse: ResidualGraph,
-) -> Cut {
-    // we assume that the given paths are valid for the given residual graph, hence this works
-    let destination = Path::get_destination_node_index(&paths);
-    let source = Path::get_source_node_index(&paths);
+fn total_weight<F: Fn(usize) -> u64>(edges: &[usize], weight_of: F) -> Result<u64, Error> {
+    edges.iter().try_fold(0u64, |total, &edge| {
+        total.checked_add(weight_of(edge)).ok_or(Error::Overflow)
+    })
+}
 
-    let mut destination_set = HashSet::<usize>::new();
-    // find reachable region starting from destination using BFS
-    let mut bfs = Bfs::new(&residual_graph_reverse, destination);
-    while let Some(node) = bfs.next(&residual_graph_reverse) {
-        // stop traversing graph when we hit the source node
-        if node == source {
-            continue;
+impl PartialOrd for ImportantCut {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Important cuts are ordered by their size, and cuts of the same size by their sorted edges.
+impl Ord for ImportantCut {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.edge_indices
+            .len()
+            .cmp(&other.edge_indices.len())
+            .then_with(|| self.edge_indices.cmp(&other.edge_indices))
+    }
+}
+
+/// Maps each edge to the positions of the cuts it appears in, e.g. to find edges that are shared
+/// by many important cuts.
+pub fn edge_cut_membership(cuts: &[ImportantCut]) -> HashMap<usize, Vec<usize>> {
+    let mut membership = HashMap::<usize, Vec<usize>>::new();
+    for (cut_index, cut) in cuts.iter().enumerate() {
+        for &edge in &cut.edge_indices {
+            membership.entry(edge).or_default().push(cut_index);
         }
-        destination_set.insert(NodeIndexable::to_index(&residual_graph_reverse, node));
     }
-    let mut source_set = HashSet::<usize>::from_iter(0..residual_graph_reverse.node_count());
-    source_set = source_set
-        .difference(&destination_set)
-        .map(|i| *i)
-        .collect();
+    membership
+}
+
+/// Builds the minimum cut whose destination side is the given set of vertices, where the cut
+/// edges are the edges in which the paths cross from the source side to the destination side.
+fn minimum_cut_from_destination_side(
+    paths: &[Path],
+    node_count: usize,
+    destination_set: HashSet<usize>,
+) -> Cut {
+    let mut source_set = HashSet::<usize>::from_iter(0..node_count);
+    source_set = source_set.difference(&destination_set).copied().collect();
 
     let mut cut_edges = vec![];
     for path in paths {
@@ -163,46 +394,110 @@ fn generate_minimum_cut_closest_to_destination(
     )
 }
 
-pub fn generate_minimum_cut_closest_to_destination_with_mapping(
-    paths: &Vec<Path>,
-    residual_graph_reverse: ResidualGraph,
-    index_mapping: IndexMapping,
+pub(crate) fn generate_minimum_cut_closest_to_destination(
+    paths: &[Path],
+    residual_graph_reverse: &ResidualGraph,
 ) -> Cut {
-    let min_cut_contracted =
-        generate_minimum_cut_closest_to_destination(paths, residual_graph_reverse);
-
-    let mut source_set_mapped = vec![];
-    let mut destination_set_mapped = vec![];
-    let mut edge_set_mapped = vec![];
+    // we assume that the given paths are valid for the given residual graph, hence this works
+    let destination = Path::get_destination_node_index(paths);
+    let source = Path::get_source_node_index(paths);
 
-    for source_vertex in min_cut_contracted.source_set {
-        match index_mapping
-            .vertex_contracted_to_original
-            .get(&source_vertex)
-        {
-            None => panic!("Index mapping missing entry for vertex {}", source_vertex),
-            Some(values) => source_set_mapped.extend(values.clone()),
+    let mut destination_set = HashSet::<usize>::new();
+    // find reachable region starting from destination using BFS
+    let mut bfs = Bfs::new(residual_graph_reverse, destination);
+    while let Some(node) = bfs.next(residual_graph_reverse) {
+        // stop traversing graph when we hit the source node
+        if node == source {
+            continue;
         }
+        destination_set.insert(NodeIndexable::to_index(residual_graph_reverse, node));
     }
 
-    for dest_vertex in min_cut_contracted.destination_set {
-        match index_mapping
-            .vertex_contracted_to_original
-            .get(&dest_vertex)
-        {
-            None => panic!("Index mapping missing entry for vertex {}", dest_vertex),
-            Some(values) => destination_set_mapped.extend(values.clone()),
-        }
+    minimum_cut_from_destination_side(paths, residual_graph_reverse.node_count(), destination_set)
+}
+
+pub(crate) fn generate_minimum_cut_closest_to_source(
+    paths: &[Path],
+    residual_graph_reverse: &ResidualGraph,
+) -> Cut {
+    let source = Path::get_source_node_index(paths);
+
+    // the arcs of the residual graph are the reverse arcs of our reverse residual graph, so we
+    // find the region reachable from the source by traversing the arcs backwards
+    let residual_graph = Reversed(residual_graph_reverse);
+    let mut source_set = HashSet::<usize>::new();
+    let mut bfs = Bfs::new(residual_graph, source);
+    while let Some(node) = bfs.next(residual_graph) {
+        source_set.insert(node.index());
     }
+    let destination_set = (0..residual_graph_reverse.node_count())
+        .filter(|vertex| !source_set.contains(vertex))
+        .collect();
+
+    minimum_cut_from_destination_side(paths, residual_graph_reverse.node_count(), destination_set)
+}
+
+/// Maps a cut of a contracted graph, e.g. one created by
+/// [`contract_vertices`](crate::cuts::contract_vertices), back to the vertices and edges of the
+/// graph that was contracted. Panics if the index mapping has no entry for a vertex or an edge of
+/// the cut.
+pub fn expand_cut(cut: &Cut, index_mapping: &IndexMapping) -> Cut {
+    map_cut_to_original(cut.clone(), index_mapping).unwrap_or_else(|error| panic!("{}", error))
+}
 
-    for cut_edge in min_cut_contracted.cut_edge_set {
-        match index_mapping.edge_contracted_to_original.get(&cut_edge) {
-            None => panic!("Index mapping missing entry for edge {}", cut_edge),
-            Some(values) => edge_set_mapped.extend(values.clone()),
+/// Maps a cut of a contracted graph to the vertices and edges of the original graph. Fails if the
+/// index mapping has no entry for a vertex or an edge of the cut.
+pub(crate) fn map_cut_to_original(cut: Cut, index_mapping: &IndexMapping) -> Result<Cut, Error> {
+    let map_vertices = |vertices: Vec<usize>| -> Result<Vec<usize>, Error> {
+        let mut mapped = vec![];
+        for vertex in vertices {
+            match index_mapping.original_vertices(vertex) {
+                None => {
+                    return Err(Error::MissingMapping {
+                        kind: MappingKind::Vertex,
+                        index: vertex,
+                    })
+                }
+                Some(values) => mapped.extend_from_slice(values),
+            }
+        }
+        Ok(mapped)
+    };
+    let source_set_mapped = map_vertices(cut.source_set)?;
+    let destination_set_mapped = map_vertices(cut.destination_set)?;
+
+    // a contracted edge that merges several original edges is crossed by one path per original
+    // edge, so we only map each contracted edge once
+    let mut edge_set_mapped = vec![];
+    for cut_edge in cut.cut_edge_set.into_iter().unique() {
+        match index_mapping.original_edges(cut_edge) {
+            None => {
+                return Err(Error::MissingMapping {
+                    kind: MappingKind::Edge,
+                    index: cut_edge,
+                })
+            }
+            Some(values) => edge_set_mapped.extend_from_slice(values),
         }
     }
 
-    Cut::new(source_set_mapped, destination_set_mapped, edge_set_mapped)
+    Ok(Cut::new(
+        source_set_mapped,
+        destination_set_mapped,
+        edge_set_mapped,
+    ))
+}
+
+/// Finds the minimum cut closest to the destination and maps it to the vertices and edges of the
+/// original graph. Fails if the index mapping does not belong to the residual graph.
+pub fn generate_minimum_cut_closest_to_destination_with_mapping(
+    paths: &[Path],
+    residual_graph_reverse: ResidualGraph,
+    index_mapping: IndexMapping,
+) -> Result<Cut, Error> {
+    let min_cut_contracted =
+        generate_minimum_cut_closest_to_destination(paths, &residual_graph_reverse);
+    map_cut_to_original(min_cut_contracted, &index_mapping)
 }
 
 #[cfg(test)]
@@ -214,13 +509,14 @@ mod tests {
     use petgraph::visit::NodeIndexable;
 
     use crate::cuts::cut::{
-        generate_minimum_cut_closest_to_destination,
+        edge_cut_membership, expand_cut, generate_minimum_cut_closest_to_destination,
         generate_minimum_cut_closest_to_destination_with_mapping, ImportantCut,
     };
     use crate::cuts::path_residual::{
         get_augmenting_paths_and_residual_graph, IndexMapping, Path, ResidualGraph,
     };
     use crate::cuts::{path_residual, Cut};
+    use crate::{Error, MappingKind};
 
     fn all_contained(lhs: Vec<usize>, rhs: Vec<usize>) -> bool {
         lhs.iter().all(|elem| rhs.contains(elem))
@@ -233,7 +529,7 @@ mod tests {
     #[test]
     fn correct_minimum_graph_generation() {
         // TODO Maybe this test (and the one below) could benefit from a visualization?
-        let residual_graph_reverse = ResidualGraph::from_edges(&[
+        let residual_graph_reverse = ResidualGraph::from_edges([
             // bidirectional edges
             (0, 1),
             (1, 0),
@@ -263,7 +559,7 @@ mod tests {
             },
         ];
 
-        let cut = generate_minimum_cut_closest_to_destination(&paths, residual_graph_reverse);
+        let cut = generate_minimum_cut_closest_to_destination(&paths, &residual_graph_reverse);
 
         let expected_source_set: Vec<usize> = vec![0, 1, 2, 3, 4, 5, 6];
         let expected_destination_set: Vec<usize> = vec![7];
@@ -277,7 +573,7 @@ mod tests {
 
     #[test]
     fn correct_minimum_graph_generation_from_graph() {
-        let graph = graph::UnGraph::<(), ()>::from_edges(&[
+        let graph = graph::UnGraph::<(), ()>::from_edges([
             (0, 1),
             (0, 2),
             (0, 3),
@@ -300,7 +596,7 @@ mod tests {
             2,
             &mut vec![1; graph.edge_count()],
         ) {
-            let cut_r_max = generate_minimum_cut_closest_to_destination(&paths, residual_reverse);
+            let cut_r_max = generate_minimum_cut_closest_to_destination(&paths, &residual_reverse);
 
             let expected_source_set_rev: Vec<usize> = vec![0, 1, 2, 3, 4, 5, 6];
             let expected_destination_set_rev: Vec<usize> = vec![7];
@@ -317,22 +613,79 @@ mod tests {
                 cut_r_max.cut_edge_set
             ));
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
+    #[test]
+    fn weight_of_cut() {
+        let cut = Cut::new(vec![0], vec![1, 2], vec![0, 2]);
+        assert_eq!(7, cut.weight(|edge| [3, 100, 4][edge]));
+        assert_eq!(
+            Err(Error::Overflow),
+            cut.try_weight(|edge| [u64::MAX - 1, 0, u64::MAX - 1][edge])
+        );
+    }
+
     #[test]
     fn test_get_arbitrary_edge() {
-        let graph = path_residual::UnGraph::from_edges(&[(0, 1), (2, 1), (2, 3)]);
+        let graph = path_residual::UnGraph::from_edges([(0, 1), (2, 1), (2, 3)]);
         let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1]);
 
         let arbitrary_edge = cut.arbitrary_edge(&graph);
-        assert_eq!((1, 2), arbitrary_edge);
+        assert_eq!(Ok((1, 2)), arbitrary_edge);
+    }
+
+    #[test]
+    fn separates_pairs_across_the_cut() {
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1]);
+
+        assert!(cut.separates(0, 3));
+        assert!(cut.separates(1, 2));
+        assert!(!cut.separates(0, 1));
+        assert!(!cut.separates(2, 3));
+        assert!(!cut.separates(3, 0));
+        assert!(!cut.separates(0, 4));
+    }
+
+    #[test]
+    fn union_of_overlapping_source_sides() {
+        // on a cycle, the two source sides reach from 0 in opposite directions
+        let graph =
+            path_residual::UnGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0)]);
+        let first = Cut::new(vec![0, 1, 2], vec![3, 4, 5], vec![2, 5]);
+        let second = Cut::new(vec![0, 4, 5], vec![1, 2, 3], vec![0, 3]);
+
+        let union = first.union_source(&second, &graph);
+        assert_eq!(vec![0, 1, 2, 4, 5], union.source_set);
+        assert_eq!(vec![3], union.destination_set);
+        assert_eq!(vec![2, 3], union.cut_edge_set);
+        assert_eq!(2, union.size);
+    }
+
+    #[test]
+    fn arbitrary_edge_of_invalid_cuts() {
+        let graph = path_residual::UnGraph::from_edges([(0, 1), (2, 1), (2, 3)]);
+
+        let empty_cut = Cut::new(vec![0, 1], vec![2, 3], vec![]);
+        assert_eq!(Err(Error::EmptyCut), empty_cut.arbitrary_edge(&graph));
+
+        let not_crossing = Cut::new(vec![0, 1], vec![2, 3], vec![2]);
+        assert_eq!(
+            Err(Error::IllegalEndpoint(2)),
+            not_crossing.arbitrary_edge(&graph)
+        );
+
+        let missing_edge = Cut::new(vec![0, 1], vec![2, 3], vec![7]);
+        assert_eq!(
+            Err(Error::IllegalEndpoint(7)),
+            missing_edge.arbitrary_edge(&graph)
+        );
     }
 
     #[test]
     fn correct_minimum_cut_generation_with_mapping() {
-        let contracted_graph = path_residual::UnGraph::from_edges(&[(0, 1), (0, 2), (1, 2)]);
+        let contracted_graph = path_residual::UnGraph::from_edges([(0, 1), (0, 2), (1, 2)]);
         let source = NodeIndex::from(0);
         let destination = NodeIndex::from(2);
         let index_mapping = IndexMapping::from(
@@ -351,7 +704,8 @@ mod tests {
                 &paths,
                 residual_reverse,
                 index_mapping,
-            );
+            )
+            .unwrap();
 
             let expected_source_set: Vec<usize> = vec![0, 1, 2];
             let expected_destination_set: Vec<usize> = vec![3, 4];
@@ -369,14 +723,59 @@ mod tests {
                 cut_r_max.cut_edge_set.clone()
             ));
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
+    #[test]
+    fn expand_contracted_cut() {
+        let index_mapping = IndexMapping::from(
+            HashMap::from([(0, vec![0, 1]), (1, vec![2]), (2, vec![3, 4])]),
+            HashMap::from([(0, vec![1]), (1, vec![2, 3]), (2, vec![4])]),
+        );
+        let contracted_cut = Cut::new(vec![0, 1], vec![2], vec![1, 2]);
+
+        let cut = expand_cut(&contracted_cut, &index_mapping);
+        assert_eq!(vec![0, 1, 2], cut.source_set);
+        assert_eq!(vec![3, 4], cut.destination_set);
+        assert_eq!(vec![2, 3, 4], cut.cut_edge_set);
+        assert_eq!(3, cut.size);
+    }
+
+    #[test]
+    fn minimum_cut_with_incomplete_mapping() {
+        let contracted_graph = path_residual::UnGraph::from_edges([(0, 1), (1, 2)]);
+        // the mapping has no entry for contracted edge 1
+        let index_mapping = IndexMapping::from(
+            HashMap::from([(0, vec![0]), (1, vec![1]), (2, vec![2])]),
+            HashMap::from([(0, vec![0])]),
+        );
+
+        let (paths, residual_reverse) = get_augmenting_paths_and_residual_graph(
+            &contracted_graph,
+            NodeIndex::from(0),
+            NodeIndex::from(2),
+            1,
+            &[1, 1],
+        )
+        .unwrap();
+        assert_eq!(
+            Err(Error::MissingMapping {
+                kind: MappingKind::Edge,
+                index: 1
+            }),
+            generate_minimum_cut_closest_to_destination_with_mapping(
+                &paths,
+                residual_reverse,
+                index_mapping
+            )
+        );
+    }
+
     #[test]
     fn important_cut_get_vertex_pairs() {
         let graph =
-            graph::UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 4), (0, 3), (1, 2), (2, 3)]);
+            graph::UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 4), (0, 3), (1, 2), (2, 3)]);
 
         let important_cut = ImportantCut::from(vec![0, 2, 3]);
 
@@ -385,4 +784,104 @@ mod tests {
         let expected_pairs = vec![(0, 1), (1, 4), (0, 3)];
         assert!(all_pairs_contained(expected_pairs, pairs));
     }
+
+    #[test]
+    fn sides_of_important_cut() {
+        let graph = path_residual::UnGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let important_cut = ImportantCut::from(vec![3]);
+
+        assert_eq!(vec![0, 1, 2, 3], important_cut.source_side(&graph, &[0]));
+        assert_eq!(vec![4], important_cut.destination_side(&graph, &[0]));
+    }
+
+    #[test]
+    fn edge_bitset_has_exactly_the_cut_edges() {
+        let cut = ImportantCut::from(vec![2, 3, 1]);
+        let bitset = cut.edge_bitset(6);
+
+        assert_eq!(6, bitset.len());
+        assert_eq!(vec![1, 2, 3], bitset.ones().collect::<Vec<_>>());
+
+        let other = ImportantCut::from(vec![0, 1]).edge_bitset(6);
+        assert_eq!(vec![1], (&bitset & &other).ones().collect::<Vec<_>>());
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            (&bitset | &other).ones().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn symmetric_difference_of_overlapping_cuts() {
+        let cut = ImportantCut::from(vec![0, 4, 5]);
+        let other = ImportantCut::from(vec![5, 1, 0]);
+
+        assert_eq!(vec![1, 4], cut.symmetric_difference(&other));
+        assert_eq!(vec![1, 4], other.symmetric_difference(&cut));
+        assert!(cut.symmetric_difference(&cut).is_empty());
+    }
+
+    #[test]
+    fn edges_map_to_cuts_containing_them() {
+        // the important cuts of the binary tree with three levels from the root to the leaves
+        let cuts = vec![
+            ImportantCut::from(vec![0, 1]),
+            ImportantCut::from(vec![0, 4, 5]),
+            ImportantCut::from(vec![2, 3, 1]),
+        ];
+
+        let membership = edge_cut_membership(&cuts);
+        assert_eq!(6, membership.len());
+        assert_eq!(vec![0, 1], membership[&0]);
+        assert_eq!(vec![0, 2], membership[&1]);
+        assert_eq!(vec![2], membership[&2]);
+        assert_eq!(vec![1], membership[&5]);
+    }
+
+    #[test]
+    fn try_new_removes_duplicate_edges() {
+        let cut = Cut::try_new(vec![0, 1], vec![2, 3], vec![1, 2, 1]).unwrap();
+        assert_eq!(vec![1, 2], cut.cut_edge_set);
+        assert_eq!(2, cut.size);
+    }
+
+    #[test]
+    fn try_new_rejects_overlapping_sides() {
+        assert_eq!(
+            Err(Error::OverlappingSides(1)),
+            Cut::try_new(vec![0, 1], vec![1, 2], vec![0])
+        );
+    }
+
+    #[test]
+    fn important_cuts_sort_by_size_then_edges() {
+        let mut cuts = vec![
+            ImportantCut::from(vec![4, 1, 2]),
+            ImportantCut::from(vec![3]),
+            ImportantCut::from(vec![2, 5]),
+            ImportantCut::from(vec![0, 7]),
+        ];
+        cuts.sort();
+
+        let sizes = cuts
+            .iter()
+            .map(|cut| cut.edge_indices.len())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1, 2, 2, 3], sizes);
+        assert_eq!(
+            vec![vec![3], vec![0, 7], vec![2, 5], vec![1, 2, 4]],
+            ImportantCut::vec_edge_indices(cuts)
+        );
+        assert_eq!(
+            ImportantCut::from(vec![1, 0]),
+            ImportantCut::from(vec![0, 1])
+        );
+    }
+
+    #[test]
+    fn important_cut_edges_are_canonical() {
+        assert_eq!(
+            vec![0, 2, 5],
+            ImportantCut::from(vec![5, 2, 2, 0]).edge_indices
+        );
+    }
 }
@@ -0,0 +1,210 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+
+use crate::cuts::cut::{Cut, ImportantCut};
+
+/// Writes the edges of the cut as CSV, with a header `edge_index,source,target` followed by a row
+/// for each cut edge with its endpoints in the graph.
+pub fn cut_to_csv<G>(graph: G, cut: &Cut) -> String
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut csv = String::from("edge_index,source,target\n");
+    for (edge, (source, target)) in cut.cut_edge_set.iter().zip(cut.cut_edge_endpoints(graph)) {
+        csv.push_str(&format!("{},{},{}\n", edge, source, target));
+    }
+    csv
+}
+
+/// Dense matrices of the graph and the cut, with a row and a column for each vertex index up to the
+/// node bound of the graph. The first matrix is the symmetric adjacency matrix, whose entries count
+/// the edges between two vertices up to `u8::MAX`, and the second one marks the vertex pairs that
+/// are joined by a cut edge.
+pub fn cut_to_matrices<G>(graph: G, cut: &Cut) -> (Vec<Vec<u8>>, Vec<Vec<bool>>)
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let node_bound = graph.node_bound();
+    let mut adjacency = vec![vec![0u8; node_bound]; node_bound];
+    for edge in graph.edge_references() {
+        let source = NodeIndexable::to_index(&graph, edge.source());
+        let target = NodeIndexable::to_index(&graph, edge.target());
+        adjacency[source][target] = adjacency[source][target].saturating_add(1);
+        if source != target {
+            adjacency[target][source] = adjacency[target][source].saturating_add(1);
+        }
+    }
+
+    let mut cut_mask = vec![vec![false; node_bound]; node_bound];
+    for (source, target) in cut.cut_edge_endpoints(graph) {
+        cut_mask[source][target] = true;
+        cut_mask[target][source] = true;
+    }
+    (adjacency, cut_mask)
+}
+
+/// Formats in which [`write_important_cuts_to_path`] can write the cuts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A list of the vertex pairs of each cut, see [`important_cuts_to_text`]
+    Text,
+    /// A JSON array of the cuts, see [`important_cuts_to_json`]
+    #[cfg(feature = "serde")]
+    Json,
+    /// A row for each edge of each cut, see [`important_cuts_to_csv`]
+    Csv,
+}
+
+/// Lists the endpoints of the edges of each cut, in the format of
+/// [`ImportantCut::print_important_cuts`].
+pub fn important_cuts_to_text<G>(graph: G, cuts: &[ImportantCut]) -> String
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut text = String::from("Important cuts:\n");
+    for vertex_pairs in ImportantCut::vec_vertex_indices(graph, cuts.to_vec()) {
+        text.push_str(&format!("- {:?}\n", vertex_pairs));
+    }
+    text
+}
+
+/// Writes the cuts as a JSON array of objects holding the edge indices of each cut.
+#[cfg(feature = "serde")]
+pub fn important_cuts_to_json(cuts: &[ImportantCut]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(cuts)
+}
+
+/// Writes the cuts as CSV, with a header `cut_index,edge_index,source,target` followed by a row
+/// for each edge of each cut with its endpoints in the graph.
+pub fn important_cuts_to_csv<G>(graph: G, cuts: &[ImportantCut]) -> String
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut csv = String::from("cut_index,edge_index,source,target\n");
+    for (cut_index, cut) in cuts.iter().enumerate() {
+        for (edge, (source, target)) in cut.edge_indices().iter().zip(cut.vertex_pairs(graph)) {
+            csv.push_str(&format!("{},{},{},{}\n", cut_index, edge, source, target));
+        }
+    }
+    csv
+}
+
+/// Writes the cuts to the file at the given path in the given format, replacing the file if it
+/// exists.
+pub fn write_important_cuts_to_path<G, P>(
+    path: P,
+    graph: G,
+    cuts: &[ImportantCut],
+    format: OutputFormat,
+) -> io::Result<()>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    P: AsRef<Path>,
+{
+    let contents = match format {
+        OutputFormat::Text => important_cuts_to_text(graph, cuts),
+        #[cfg(feature = "serde")]
+        OutputFormat::Json => important_cuts_to_json(cuts)?,
+        OutputFormat::Csv => important_cuts_to_csv(graph, cuts),
+    };
+    fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use crate::cuts::export::{
+        cut_to_csv, cut_to_matrices, important_cuts_to_csv, important_cuts_to_text,
+    };
+    use crate::cuts::{Cut, ImportantCut};
+
+    #[test]
+    fn csv_of_cut_edges() {
+        // 0 - 1 - 2 - 3, where 1 - 3 is an edge as well
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 3]);
+
+        let csv = cut_to_csv(&graph, &cut);
+        let mut lines = csv.lines();
+        let header = lines.next().unwrap().split(',').collect::<Vec<_>>();
+        assert_eq!(vec!["edge_index", "source", "target"], header);
+
+        let rows = lines
+            .map(|line| {
+                line.split(',')
+                    .map(|field| field.parse::<usize>().unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(cut.size, rows.len());
+        assert_eq!(vec![vec![1, 1, 2], vec![3, 1, 3]], rows);
+    }
+
+    #[test]
+    fn matrices_of_cut() {
+        // 0 - 1 - 2 - 3, where 1 - 3 is an edge as well
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 3]);
+
+        let (adjacency, cut_mask) = cut_to_matrices(&graph, &cut);
+        assert_eq!(
+            vec![
+                vec![0, 1, 0, 0],
+                vec![1, 0, 1, 1],
+                vec![0, 1, 0, 1],
+                vec![0, 1, 1, 0],
+            ],
+            adjacency
+        );
+        for i in 0..4 {
+            for j in 0..4 {
+                assert_eq!(adjacency[i][j], adjacency[j][i]);
+                assert_eq!(cut_mask[i][j], cut_mask[j][i]);
+            }
+        }
+
+        let marked = (0..4)
+            .flat_map(|i| (i..4).map(move |j| (i, j)))
+            .filter(|&(i, j)| cut_mask[i][j])
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(1, 2), (1, 3)], marked);
+    }
+
+    #[test]
+    fn important_cuts_as_text_and_csv() {
+        // 0 - 1 - 2 - 3
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+        let cuts = vec![ImportantCut::from(vec![2]), ImportantCut::from(vec![0, 1])];
+
+        assert_eq!(
+            "Important cuts:\n- [(2, 3)]\n- [(0, 1), (1, 2)]\n",
+            important_cuts_to_text(&graph, &cuts)
+        );
+        assert_eq!(
+            "cut_index,edge_index,source,target\n0,2,2,3\n1,0,0,1\n1,1,1,2\n",
+            important_cuts_to_csv(&graph, &cuts)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn write_json_to_path() {
+        use crate::cuts::export::{write_important_cuts_to_path, OutputFormat};
+
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+        let cuts = vec![ImportantCut::from(vec![2]), ImportantCut::from(vec![0, 1])];
+        let path = std::env::temp_dir().join(format!("important_cuts_{}.json", std::process::id()));
+
+        write_important_cuts_to_path(&path, &graph, &cuts, OutputFormat::Json).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let value = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+        assert_eq!(2, value.as_array().unwrap().len());
+        assert_eq!(serde_json::json!([0, 1]), value[1]["edge_indices"]);
+    }
+}
@@ -0,0 +1,535 @@
+use std::collections::{HashMap, VecDeque};
+
+use petgraph::visit::{
+    EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoEdges, NodeIndexable, VisitMap, Visitable,
+};
+
+use crate::cuts::path_residual::Path;
+
+// Based on petgraph::algo::ford_fulkerson
+
+/// Gets the other endpoint of graph edge. The vertex has to be an endpoint of the edge.
+pub(crate) fn other_endpoint<G>(graph: G, edge: G::EdgeRef, vertex: G::NodeId) -> G::NodeId
+where
+    G: NodeIndexable + IntoEdges,
+{
+    debug_assert!(
+        vertex == edge.source() || vertex == edge.target(),
+        "Illegal endpoint {}",
+        NodeIndexable::to_index(&graph, vertex)
+    );
+    if vertex == edge.source() {
+        edge.target()
+    } else {
+        edge.source()
+    }
+}
+
+/// Direction of the flow from `from` to `to` relative to the stored flow of an undirected edge,
+/// which is positive if it goes from the endpoint with the smaller index to the larger one.
+pub(crate) fn flow_direction(from: usize, to: usize) -> isize {
+    if from < to {
+        1
+    } else {
+        -1
+    }
+}
+
+/// Residual capacity of an edge with the given flow when traversed from `from` to `to`.
+pub(crate) fn residual_capacity(capacity: usize, flow: isize, from: usize, to: usize) -> isize {
+    capacity as isize - flow_direction(from, to) * flow
+}
+
+/// Finds a shortest augmenting path from the source to the destination by a BFS through the
+/// residual graph. The path is stored in `next_edge`, which gives for each vertex on it the edge
+/// through which it was reached. With the `deterministic` feature, the edges of a vertex are
+/// visited in the order of their other endpoints, so that the path does not depend on the order in
+/// which the edges were added to the graph.
+pub(crate) fn has_augmenting_path<G>(
+    graph: G,
+    source: G::NodeId,
+    destination: G::NodeId,
+    next_edge: &mut [Option<G::EdgeRef>],
+    edge_capacities: &[usize],
+    edge_flows: &[isize],
+) -> bool
+where
+    G: NodeIndexable + EdgeIndexable + Visitable + IntoEdges,
+{
+    augmenting_path_between_sets(
+        graph,
+        &[source],
+        &[destination],
+        next_edge,
+        edge_capacities,
+        edge_flows,
+    )
+    .is_some()
+}
+
+/// Same as [`has_augmenting_path`], but the BFS starts from all vertices of the source set at once
+/// and stops at the first vertex of the destination set that it reaches, which is returned. This
+/// finds the same paths as contracting each set into a single vertex, without building the
+/// contracted graph.
+pub(crate) fn augmenting_path_between_sets<G>(
+    graph: G,
+    source_set: &[G::NodeId],
+    destination_set: &[G::NodeId],
+    next_edge: &mut [Option<G::EdgeRef>],
+    edge_capacities: &[usize],
+    edge_flows: &[isize],
+) -> Option<G::NodeId>
+where
+    G: NodeIndexable + EdgeIndexable + Visitable + IntoEdges,
+{
+    let mut visited = graph.visit_map();
+    let mut queue: VecDeque<G::NodeId> = VecDeque::new();
+    for &source in source_set {
+        visited.visit(source);
+        queue.push_back(source);
+    }
+
+    // do a BFS through the residual graph, where an edge can also be traversed against its flow
+    while let Some(vertex) = queue.pop_front() {
+        let vertex_index = NodeIndexable::to_index(&graph, vertex);
+        #[cfg(feature = "deterministic")]
+        let edges = {
+            let mut edges = graph.edges(vertex).collect::<Vec<_>>();
+            edges.sort_by_key(|&edge| {
+                (
+                    NodeIndexable::to_index(&graph, other_endpoint(&graph, edge, vertex)),
+                    EdgeIndexable::to_index(&graph, edge.id()),
+                )
+            });
+            edges
+        };
+        #[cfg(not(feature = "deterministic"))]
+        let edges = graph.edges(vertex);
+        for edge in edges {
+            let next = other_endpoint(&graph, edge, vertex);
+            let next_index = NodeIndexable::to_index(&graph, next);
+            let edge_index: usize = EdgeIndexable::to_index(&graph, edge.id());
+            let edge_available = residual_capacity(
+                edge_capacities[edge_index],
+                edge_flows[edge_index],
+                vertex_index,
+                next_index,
+            ) > 0;
+            if !visited.is_visited(&next) && edge_available {
+                next_edge[next_index] = Some(edge);
+                if destination_set.contains(&next) {
+                    // we've found an augmenting path
+                    return Some(next);
+                }
+                visited.visit(next);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Value of the maximum flow with unit capacities between the source and destination sets, which
+/// have to be disjoint. The flow is pushed from all source vertices at once, so neither set has to
+/// be contracted.
+pub(crate) fn max_flow_value_between_sets<G>(
+    graph: G,
+    source_set: &[usize],
+    destination_set: &[usize],
+) -> usize
+where
+    G: NodeIndexable + EdgeIndexable + Visitable + IntoEdges,
+{
+    let edge_capacities = vec![1; graph.edge_bound()];
+    max_flow_with_capacities_between_sets(
+        graph,
+        source_set,
+        destination_set,
+        &edge_capacities,
+        usize::MAX,
+    )
+    .0
+}
+
+/// Maximum flow between the source and destination sets, which have to be disjoint, where each
+/// edge can carry as much flow as its capacity in either direction. Returns the value of the flow
+/// and the flow of each edge. Each augmenting path is a shortest one and saturates at least one
+/// edge, so the number of augmentations does not depend on the capacities, which have to fit into
+/// an `isize`. The search stops as soon as the value of the flow is larger than `max_value`.
+///
+/// No flow enters the source set or leaves the destination set, because the search starts from
+/// all source vertices at once and stops at the first destination vertex it reaches.
+pub(crate) fn max_flow_with_capacities_between_sets<G>(
+    graph: G,
+    source_set: &[usize],
+    destination_set: &[usize],
+    edge_capacities: &[usize],
+    max_value: usize,
+) -> (usize, Vec<isize>)
+where
+    G: NodeIndexable + EdgeIndexable + Visitable + IntoEdges,
+{
+    let edge_flows = vec![0isize; graph.edge_bound()];
+    augment_flow_between_sets(
+        graph,
+        source_set,
+        destination_set,
+        edge_capacities,
+        max_value,
+        (0, edge_flows),
+    )
+}
+
+/// Same as [`max_flow_with_capacities_between_sets`], but starts from the given flow value and
+/// flow of each edge instead of an empty flow, e.g. a maximum flow that is kept up to date while
+/// the graph changes. The given flow has to respect the capacities, and no flow may enter the
+/// source set or leave the destination set.
+pub(crate) fn augment_flow_between_sets<G>(
+    graph: G,
+    source_set: &[usize],
+    destination_set: &[usize],
+    edge_capacities: &[usize],
+    max_value: usize,
+    (mut flow_value, mut edge_flows): (usize, Vec<isize>),
+) -> (usize, Vec<isize>)
+where
+    G: NodeIndexable + EdgeIndexable + Visitable + IntoEdges,
+{
+    debug_assert!(
+        source_set
+            .iter()
+            .all(|vertex| !destination_set.contains(vertex)),
+        "The source and destination sets should be disjoint"
+    );
+    let source_set = source_set
+        .iter()
+        .map(|&vertex| NodeIndexable::from_index(&graph, vertex))
+        .collect::<Vec<_>>();
+    let destination_set = destination_set
+        .iter()
+        .map(|&vertex| NodeIndexable::from_index(&graph, vertex))
+        .collect::<Vec<_>>();
+    let mut next_edge = vec![None; graph.node_bound()];
+
+    while flow_value <= max_value {
+        let Some(destination) = augmenting_path_between_sets(
+            &graph,
+            &source_set,
+            &destination_set,
+            &mut next_edge,
+            edge_capacities,
+            &edge_flows,
+        ) else {
+            break;
+        };
+        // walk back along the path to the source vertex it started from, once to find the
+        // smallest residual capacity on it and once to push that much flow
+        let mut path = vec![];
+        let mut vertex = destination;
+        while !source_set.contains(&vertex) {
+            let edge = next_edge[NodeIndexable::to_index(&graph, vertex)]
+                .expect("Path should lead back to the source set");
+            let previous = other_endpoint(&graph, edge, vertex);
+            path.push((
+                EdgeIndexable::to_index(&graph, edge.id()),
+                NodeIndexable::to_index(&graph, previous),
+                NodeIndexable::to_index(&graph, vertex),
+            ));
+            vertex = previous;
+        }
+        let bottleneck = path
+            .iter()
+            .map(|&(edge, from, to)| {
+                residual_capacity(edge_capacities[edge], edge_flows[edge], from, to)
+            })
+            .min()
+            .expect("An augmenting path should have an edge");
+        for &(edge, from, to) in &path {
+            edge_flows[edge] += flow_direction(from, to) * bottleneck;
+        }
+        flow_value += bottleneck as usize;
+    }
+    (flow_value, edge_flows)
+}
+
+/// Vertices from which the destination set can be reached through the residual graph of the flow,
+/// as a mask. They are the destination side of the minimum cut closest to the destination set.
+pub(crate) fn vertices_reaching_destination<G>(
+    graph: G,
+    destination_set: &[usize],
+    edge_capacities: &[usize],
+    edge_flows: &[isize],
+) -> Vec<bool>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdges,
+{
+    let mut reaching = vec![false; graph.node_bound()];
+    let mut queue = VecDeque::new();
+    for &vertex in destination_set {
+        reaching[vertex] = true;
+        queue.push_back(vertex);
+    }
+    while let Some(vertex) = queue.pop_front() {
+        for edge in graph.edges(NodeIndexable::from_index(&graph, vertex)) {
+            let previous = NodeIndexable::to_index(
+                &graph,
+                other_endpoint(&graph, edge, NodeIndexable::from_index(&graph, vertex)),
+            );
+            let edge_index = EdgeIndexable::to_index(&graph, edge.id());
+            if !reaching[previous]
+                && residual_capacity(
+                    edge_capacities[edge_index],
+                    edge_flows[edge_index],
+                    previous,
+                    vertex,
+                ) > 0
+            {
+                reaching[previous] = true;
+                queue.push_back(previous);
+            }
+        }
+    }
+    reaching
+}
+
+/// Decomposes a flow of the given value into edge-disjoint paths from the source set to the
+/// destination set, into which no flow may enter or leave respectively. Cycles in the flow are
+/// dropped. With the `deterministic` feature, the edges of a vertex are followed in the order of
+/// their other endpoints.
+pub(crate) fn decompose_flow<G>(
+    graph: G,
+    source_set: &[usize],
+    destination_set: &[usize],
+    flow_value: usize,
+    edge_flows: &mut [isize],
+) -> Vec<Path>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    // outgoing edges of each vertex as (edge, other endpoint)
+    let mut outgoing = HashMap::<usize, Vec<(usize, usize)>>::new();
+    for edge in graph.edge_references() {
+        let edge_source = NodeIndexable::to_index(&graph, edge.source());
+        let edge_target = NodeIndexable::to_index(&graph, edge.target());
+        let edge_index = EdgeIndexable::to_index(&graph, edge.id());
+        outgoing
+            .entry(edge_source)
+            .or_default()
+            .push((edge_index, edge_target));
+        outgoing
+            .entry(edge_target)
+            .or_default()
+            .push((edge_index, edge_source));
+    }
+
+    #[cfg(feature = "deterministic")]
+    for edges in outgoing.values_mut() {
+        edges.sort_by_key(|&(edge, next)| (next, edge));
+    }
+
+    let carries_flow = |edge_flows: &[isize], vertex: usize, edge: usize, next: usize| {
+        flow_direction(vertex, next) * edge_flows[edge] > 0
+    };
+    let mut paths = vec![];
+    for _ in 0..flow_value {
+        let source = source_set
+            .iter()
+            .copied()
+            .find(|vertex| {
+                outgoing.get(vertex).is_some_and(|edges| {
+                    edges
+                        .iter()
+                        .any(|&(edge, next)| carries_flow(edge_flows, *vertex, edge, next))
+                })
+            })
+            .expect("Every unit of flow should leave the source set");
+        let mut vertices = vec![source];
+        let mut edges = vec![];
+        let mut vertex = source;
+        while !destination_set.contains(&vertex) {
+            let &(edge, next) = outgoing[&vertex]
+                .iter()
+                .find(|&&(edge, next)| carries_flow(edge_flows, vertex, edge, next))
+                .expect("Flow should be conserved at every vertex");
+            edge_flows[edge] -= flow_direction(vertex, next);
+            // if we return to a vertex of the path, the flow contains a cycle which we drop
+            match vertices.iter().position(|&path_vertex| path_vertex == next) {
+                Some(position) => {
+                    vertices.truncate(position + 1);
+                    edges.truncate(position);
+                }
+                None => {
+                    vertices.push(next);
+                    edges.push(edge);
+                }
+            }
+            vertex = next;
+        }
+        paths.push(Path { vertices, edges });
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+    use petgraph::stable_graph::NodeIndex;
+
+    use crate::cuts::cut::{
+        generate_minimum_cut_closest_to_destination, generate_minimum_cut_closest_to_source,
+    };
+    use crate::cuts::flow::{
+        decompose_flow, flow_direction, max_flow_value_between_sets,
+        max_flow_with_capacities_between_sets, residual_capacity,
+    };
+    use crate::cuts::path_residual::{
+        get_augmenting_paths_and_residual_graph, get_augmenting_paths_and_residual_graph_for_sets,
+    };
+
+    #[test]
+    fn flow_sign_is_relative_to_smaller_endpoint() {
+        assert_eq!(1, flow_direction(0, 3));
+        assert_eq!(-1, flow_direction(3, 0));
+        // one unit flows from 0 to 3, so it can be sent back twice as far from 3 to 0
+        assert_eq!(0, residual_capacity(1, 1, 0, 3));
+        assert_eq!(2, residual_capacity(1, 1, 3, 0));
+    }
+
+    #[test]
+    fn flow_from_source_set_matches_contracted_flow() {
+        //    -1-          9---6
+        //   / | \         |  /
+        //  /  |  \        | /
+        // 0---2---4---7---10
+        //  \  |          /
+        //   \ |         /
+        //    -3---5---8-
+        let graph = UnGraph::<(), (), usize>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 4),
+            (3, 5),
+            (4, 7),
+            (5, 8),
+            (7, 10),
+            (8, 10),
+            (6, 10),
+            (6, 9),
+            (9, 10),
+        ]);
+        let edge_count = graph.edge_count();
+        let contracted_flow = |source_set: &[usize], destination_set: &[usize]| {
+            get_augmenting_paths_and_residual_graph_for_sets(
+                &graph,
+                source_set.to_vec(),
+                destination_set.to_vec(),
+                edge_count,
+                &vec![true; edge_count],
+            )
+            .map_or(0, |(paths, _, _)| paths.len())
+        };
+
+        for (source_set, destination_set) in [
+            (vec![0, 1, 2], vec![9, 10]),
+            (vec![0], vec![6]),
+            (vec![1, 3], vec![7, 8]),
+            (vec![0, 4], vec![5, 6, 9]),
+        ] {
+            assert_eq!(
+                contracted_flow(&source_set, &destination_set),
+                max_flow_value_between_sets(&graph, &source_set, &destination_set)
+            );
+        }
+        assert_eq!(2, max_flow_value_between_sets(&graph, &[0, 1, 2], &[9, 10]));
+    }
+
+    #[test]
+    fn flow_paths_run_between_the_sets() {
+        // 0---2---4
+        //     |
+        // 1---3---5
+        let graph = UnGraph::<(), (), usize>::from_edges([(0, 2), (2, 4), (2, 3), (1, 3), (3, 5)]);
+        let (source_set, destination_set) = ([0, 1], [4, 5]);
+        let (flow_value, mut edge_flows) = max_flow_with_capacities_between_sets(
+            &graph,
+            &source_set,
+            &destination_set,
+            &[1; 5],
+            usize::MAX,
+        );
+        assert_eq!(2, flow_value);
+        let paths = decompose_flow(
+            &graph,
+            &source_set,
+            &destination_set,
+            flow_value,
+            &mut edge_flows,
+        );
+        assert_eq!(2, paths.len());
+        for path in &paths {
+            assert!(source_set.contains(&path.vertices[0]));
+            assert!(destination_set.contains(path.vertices.last().unwrap()));
+            assert_eq!(path.vertices.len(), path.edges.len() + 1);
+        }
+        assert!(edge_flows.iter().all(|&flow| flow == 0));
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn paths_do_not_depend_on_edge_order() {
+        // 0 - 1 - 3
+        //  \  |  /
+        //    2
+        let edges = [(0, 1), (0, 2), (1, 2), (1, 3), (2, 3)];
+        let path_vertices = |edges: &[(usize, usize)]| {
+            let graph = UnGraph::<(), (), usize>::from_edges(edges);
+            get_augmenting_paths_and_residual_graph(
+                &graph,
+                NodeIndex::new(0),
+                NodeIndex::new(3),
+                2,
+                &[1; 5],
+            )
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|path| path.vertices)
+            .collect::<Vec<_>>()
+        };
+
+        let expected = path_vertices(&edges);
+        let mut shuffled = edges;
+        shuffled.reverse();
+        assert_eq!(expected, path_vertices(&shuffled));
+        shuffled.swap(0, 3);
+        assert_eq!(expected, path_vertices(&shuffled));
+    }
+
+    #[test]
+    fn both_cut_directions_from_shared_flow() {
+        // 0 - 1 - 2, where both edges are minimum cuts
+        let graph = UnGraph::<(), (), usize>::from_edges([(0, 1), (1, 2)]);
+        let (paths, residual) = get_augmenting_paths_and_residual_graph(
+            &graph,
+            NodeIndex::new(0),
+            NodeIndex::new(2),
+            1,
+            &[1, 1],
+        )
+        .unwrap();
+        assert_eq!(vec![0, 1, 2], paths[0].vertices);
+
+        let closest_to_source = generate_minimum_cut_closest_to_source(&paths, &residual);
+        assert_eq!(vec![0], closest_to_source.cut_edge_set);
+        assert_eq!(vec![0], closest_to_source.source_set);
+
+        let closest_to_destination = generate_minimum_cut_closest_to_destination(&paths, &residual);
+        assert_eq!(vec![1], closest_to_destination.cut_edge_set);
+        assert_eq!(vec![2], closest_to_destination.destination_set);
+    }
+}
@@ -0,0 +1,269 @@
+use petgraph::visit::{
+    EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable,
+};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::cuts::compaction::Compaction;
+use crate::cuts::cut::Cut;
+
+/// Finds a global minimum cut of the graph, i.e. a minimum cut over all partitions of the vertices
+/// into two nonempty sides, using the Stoer–Wagner algorithm. Panics if the graph has fewer than
+/// two vertices.
+///
+/// The algorithm needs no terminals: each phase orders the vertices by maximum adjacency, and the
+/// last vertex of the ordering is cut off from the rest in the cut of the phase. The last two
+/// vertices are then merged, and the smallest cut of all phases is a global minimum cut. Which of
+/// the two sides is the source side of the returned cut is arbitrary.
+pub fn global_min_cut<G>(graph: G) -> Cut
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences + IntoNodeIdentifiers,
+{
+    let vertices = graph
+        .node_identifiers()
+        .map(|vertex| NodeIndexable::to_index(&graph, vertex))
+        .collect::<Vec<_>>();
+    let (compacted_graph, compaction) = Compaction::new(graph, &vertices);
+    let vertex_count = compacted_graph.node_count();
+    if vertex_count < 2 {
+        panic!("A global minimum cut needs at least two vertices");
+    }
+
+    // number of edges between each pair of merged vertices, self-loops never cross a cut
+    let mut weights = vec![vec![0; vertex_count]; vertex_count];
+    for edge in compacted_graph.edge_references() {
+        let (a, b) = (edge.source().index(), edge.target().index());
+        if a != b {
+            weights[a][b] += 1;
+            weights[b][a] += 1;
+        }
+    }
+
+    // the compacted vertices each merged vertex consists of
+    let mut merged = (0..vertex_count).map(|v| vec![v]).collect::<Vec<_>>();
+    let mut active = (0..vertex_count).collect::<Vec<_>>();
+    let mut best: Option<(usize, Vec<usize>)> = None;
+
+    while active.len() > 1 {
+        // maximum adjacency ordering starting from the first active vertex
+        let mut connectivity = vec![0; vertex_count];
+        let mut added = vec![false; vertex_count];
+        let mut previous = active[0];
+        let mut last = active[0];
+        added[last] = true;
+        for &v in &active {
+            connectivity[v] = weights[last][v];
+        }
+        for _ in 1..active.len() {
+            let next = *active
+                .iter()
+                .filter(|&&v| !added[v])
+                .max_by_key(|&&v| connectivity[v])
+                .expect("There should be a vertex left to add");
+            added[next] = true;
+            previous = last;
+            last = next;
+            for &v in &active {
+                connectivity[v] += weights[next][v];
+            }
+        }
+
+        // the cut of the phase separates the last vertex from all others
+        let cut_of_phase = connectivity[last];
+        if best.as_ref().is_none_or(|(size, _)| cut_of_phase < *size) {
+            best = Some((cut_of_phase, merged[last].clone()));
+        }
+
+        // merge the last vertex into the one before it
+        let last_weights = weights[last].clone();
+        for (v, weight) in last_weights.into_iter().enumerate() {
+            weights[previous][v] += weight;
+            weights[v][previous] += weight;
+        }
+        weights[previous][previous] = 0;
+        let last_merged = std::mem::take(&mut merged[last]);
+        merged[previous].extend(last_merged);
+        active.retain(|&v| v != last);
+    }
+
+    let (_, destination_side) = best.expect("There should be at least one phase");
+    let mut in_destination_side = vec![false; vertex_count];
+    for &v in &destination_side {
+        in_destination_side[v] = true;
+    }
+    let source_side = (0..vertex_count)
+        .filter(|&v| !in_destination_side[v])
+        .collect::<Vec<_>>();
+    let cut_edges = compacted_graph
+        .edge_references()
+        .filter(|edge| {
+            in_destination_side[edge.source().index()] != in_destination_side[edge.target().index()]
+        })
+        .map(|edge| edge.id().index())
+        .collect::<Vec<_>>();
+
+    compaction.original_cut(Cut::new(source_side, destination_side, cut_edges))
+}
+
+/// Estimates a global minimum cut of the graph with Karger's randomized contraction algorithm, and
+/// returns the smallest cut found in the given number of iterations. Panics if the graph has fewer
+/// than two vertices or no iterations are run.
+///
+/// Each iteration contracts uniformly random edges until only two contracted vertices are left,
+/// which are the two sides of the cut. A single iteration finds a given global minimum cut with
+/// probability at least `2 / (n (n - 1))`, so `n^2 ln n` iterations find it with high probability.
+/// Passing a seeded RNG makes the result reproducible.
+pub fn karger_min_cut<G, R>(graph: G, iterations: usize, rng: &mut R) -> Cut
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences + IntoNodeIdentifiers,
+    R: Rng,
+{
+    fn contracted_vertex(contracted_to: &mut [usize], vertex: usize) -> usize {
+        let mut root = vertex;
+        while contracted_to[root] != root {
+            root = contracted_to[root];
+        }
+        // point everything on the way directly to the contracted vertex
+        let mut vertex = vertex;
+        while contracted_to[vertex] != root {
+            let next = contracted_to[vertex];
+            contracted_to[vertex] = root;
+            vertex = next;
+        }
+        root
+    }
+
+    if iterations == 0 {
+        panic!("Karger's algorithm needs at least one iteration");
+    }
+    let vertices = graph
+        .node_identifiers()
+        .map(|vertex| NodeIndexable::to_index(&graph, vertex))
+        .collect::<Vec<_>>();
+    let (compacted_graph, compaction) = Compaction::new(graph, &vertices);
+    let vertex_count = compacted_graph.node_count();
+    if vertex_count < 2 {
+        panic!("A global minimum cut needs at least two vertices");
+    }
+    let edges = compacted_graph
+        .edge_references()
+        .map(|edge| {
+            (
+                edge.id().index(),
+                edge.source().index(),
+                edge.target().index(),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let mut best: Option<Cut> = None;
+    for _ in 0..iterations {
+        // contracting the edges in a random order is the same as picking random edges
+        let mut order = edges.clone();
+        order.shuffle(rng);
+        let mut contracted_to = (0..vertex_count).collect::<Vec<_>>();
+        let mut remaining = vertex_count;
+        for &(_, a, b) in &order {
+            if remaining == 2 {
+                break;
+            }
+            let (a, b) = (
+                contracted_vertex(&mut contracted_to, a),
+                contracted_vertex(&mut contracted_to, b),
+            );
+            if a != b {
+                contracted_to[b] = a;
+                remaining -= 1;
+            }
+        }
+
+        // if the graph is disconnected, more than two vertices may be left, and we cut off the
+        // one containing the first vertex
+        let destination = contracted_vertex(&mut contracted_to, 0);
+        let (destination_side, source_side): (Vec<usize>, Vec<usize>) = (0..vertex_count)
+            .partition(|&v| contracted_vertex(&mut contracted_to, v) == destination);
+        let cut_edges = edges
+            .iter()
+            .filter(|&&(_, a, b)| {
+                (contracted_vertex(&mut contracted_to, a) == destination)
+                    != (contracted_vertex(&mut contracted_to, b) == destination)
+            })
+            .map(|&(edge, _, _)| edge)
+            .collect::<Vec<_>>();
+
+        if best.as_ref().is_none_or(|cut| cut_edges.len() < cut.size) {
+            best = Some(Cut::new(source_side, destination_side, cut_edges));
+        }
+    }
+
+    compaction.original_cut(best.expect("There should be at least one iteration"))
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::cuts::global::{global_min_cut, karger_min_cut};
+    use crate::cuts::verify::verify_cut;
+
+    #[test]
+    fn two_cliques_joined_by_two_edges() {
+        // two copies of K4 on 0..4 and 4..8, joined by the edges (0, 4) and (1, 5)
+        let mut edges = vec![];
+        for offset in [0, 4] {
+            for a in 0..4 {
+                for b in a + 1..4 {
+                    edges.push((offset + a, offset + b));
+                }
+            }
+        }
+        edges.extend([(0, 4), (1, 5)]);
+        let graph = UnGraph::<(), ()>::from_edges(edges);
+
+        let cut = global_min_cut(&graph);
+        assert_eq!(2, cut.size);
+        let mut cut_edges = cut.cut_edge_set.clone();
+        cut_edges.sort();
+        assert_eq!(vec![12, 13], cut_edges);
+
+        let mut sides = [cut.source_set.clone(), cut.destination_set.clone()];
+        sides.iter_mut().for_each(|side| side.sort());
+        sides.sort();
+        assert_eq!([vec![0, 1, 2, 3], vec![4, 5, 6, 7]], sides);
+        assert!(verify_cut(
+            &graph,
+            &cut,
+            &cut.source_set,
+            &cut.destination_set
+        ));
+    }
+
+    #[test]
+    fn disconnected_graph_has_empty_global_min_cut() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2), (3, 4)]);
+
+        let cut = global_min_cut(&graph);
+        assert_eq!(0, cut.size);
+        assert!(cut.cut_edge_set.is_empty());
+    }
+
+    #[test]
+    fn karger_finds_minimum_cut_size() {
+        // two triangles joined by the single edge (2, 3)
+        let graph =
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 2), (2, 3), (3, 4), (4, 5), (3, 5)]);
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let cut = karger_min_cut(&graph, 50, &mut rng);
+        assert_eq!(global_min_cut(&graph).size, cut.size);
+        assert_eq!(vec![3], cut.cut_edge_set);
+        assert!(verify_cut(
+            &graph,
+            &cut,
+            &cut.source_set,
+            &cut.destination_set
+        ));
+    }
+}
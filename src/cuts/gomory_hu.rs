@@ -0,0 +1,151 @@
+use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::visit::{EdgeIndexable, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+use crate::cuts::compaction::Compaction;
+use crate::cuts::cut::generate_minimum_cut_closest_to_source;
+use crate::cuts::path_residual::get_augmenting_paths_and_residual_graph;
+use crate::cuts::verify::reachable_vertices;
+
+/// Builds the Gomory–Hu tree of the graph using Gusfield's algorithm, which needs `n - 1` maximum
+/// flow computations.
+///
+/// The tree has the same vertex indices as the graph, and the weight of each tree edge is the size
+/// of a minimum cut between its endpoints. The size of a minimum cut between any two vertices is
+/// then the smallest weight on the tree path between them, and removing that edge from the tree
+/// splits the vertices into the two sides of such a cut. Indices that are not in use in the graph
+/// are isolated vertices of the tree.
+pub fn gomory_hu_tree<G>(graph: G) -> UnGraph<(), usize>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences + IntoNodeIdentifiers,
+{
+    let vertices = graph
+        .node_identifiers()
+        .map(|vertex| NodeIndexable::to_index(&graph, vertex))
+        .collect::<Vec<_>>();
+    let (compacted_graph, compaction) = Compaction::new(graph, &vertices);
+    let vertices = compaction.compact_vertices(&vertices);
+    let vertex_count = vertices.len();
+    let edge_capacities = vec![1; compacted_graph.edge_count()];
+
+    // every vertex starts as a child of the first vertex, whose own entries are unused
+    let mut parent = vec![0; vertex_count];
+    let mut weight = vec![0; vertex_count];
+
+    for s in 1..vertex_count {
+        let t = parent[s];
+
+        // find the source side of a minimum cut between s and t
+        let (cut_size, source_side) = match get_augmenting_paths_and_residual_graph(
+            &compacted_graph,
+            NodeIndex::new(vertices[s]),
+            NodeIndex::new(vertices[t]),
+            compacted_graph.edge_count(),
+            &edge_capacities,
+        ) {
+            Some((paths, residual)) => {
+                let cut = generate_minimum_cut_closest_to_source(&paths, &residual);
+                (paths.len(), cut.source_set.into_iter().collect())
+            }
+            // s and t are not connected, so the component of s is a cut of size zero
+            None => (
+                0,
+                reachable_vertices(&compacted_graph, &[vertices[s]], |_| true),
+            ),
+        };
+        let in_source_side = |i: usize| source_side.contains(&vertices[i]);
+
+        weight[s] = cut_size;
+        for (i, parent_of_i) in parent.iter_mut().enumerate() {
+            if i != s && in_source_side(i) && *parent_of_i == t {
+                *parent_of_i = s;
+            }
+        }
+        if in_source_side(parent[t]) {
+            parent[s] = parent[t];
+            parent[t] = s;
+            weight[s] = weight[t];
+            weight[t] = cut_size;
+        }
+    }
+
+    let node_bound = NodeIndexable::node_bound(&graph);
+    let mut tree = UnGraph::with_capacity(node_bound, vertex_count.saturating_sub(1));
+    for _ in 0..node_bound {
+        tree.add_node(());
+    }
+    let original = compaction.original_vertices(&vertices);
+    for s in 1..vertex_count {
+        tree.add_edge(
+            NodeIndex::new(original[s]),
+            NodeIndex::new(original[parent[s]]),
+            weight[s],
+        );
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::algo::astar;
+    use petgraph::graph::{NodeIndex, UnGraph};
+
+    use crate::cuts::gomory_hu::gomory_hu_tree;
+    use crate::cuts::minimum_cut::max_flow_between_sets;
+
+    /// Smallest weight on the path between two vertices of the tree.
+    fn min_cut_from_tree(tree: &UnGraph<(), usize>, a: usize, b: usize) -> usize {
+        let (_, path) = astar(tree, NodeIndex::new(a), |v| v.index() == b, |_| 1, |_| 0)
+            .expect("Tree should be connected");
+        path.windows(2)
+            .map(|pair| {
+                let edge = tree.find_edge(pair[0], pair[1]).unwrap();
+                tree[edge]
+            })
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn tree_gives_pairwise_min_cuts() {
+        //   0 - 1       5
+        //   | X |     /   \
+        //   2 - 3 - 4 ----- 6
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (1, 3),
+            (2, 3),
+            (3, 4),
+            (4, 5),
+            (4, 6),
+            (5, 6),
+        ]);
+
+        let tree = gomory_hu_tree(&graph);
+        assert_eq!(graph.node_count(), tree.node_count());
+        assert_eq!(graph.node_count() - 1, tree.edge_count());
+        assert!(tree.edge_references().all(|edge| *edge.weight() > 0));
+
+        for (a, b) in [(0, 1), (0, 3), (2, 4), (1, 6), (5, 6), (4, 6), (0, 5)] {
+            assert_eq!(
+                max_flow_between_sets(&graph, vec![a], vec![b]),
+                min_cut_from_tree(&tree, a, b),
+                "min cut between {} and {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn tree_of_disconnected_graph() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (2, 3)]);
+
+        let tree = gomory_hu_tree(&graph);
+        assert_eq!(3, tree.edge_count());
+        assert_eq!(1, min_cut_from_tree(&tree, 0, 1));
+        assert_eq!(0, min_cut_from_tree(&tree, 0, 2));
+    }
+}
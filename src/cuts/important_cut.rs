@@ -1,155 +1,1273 @@
-use petgraph::prelude::EdgeRef;
-use petgraph::visit::{IntoEdgeReferences, NodeIndexable};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::sync::mpsc::Sender;
 
-use crate::cuts::cut::{generate_minimum_cut_closest_to_destination_with_mapping, ImportantCut};
-use crate::cuts::path_residual::{get_augmenting_paths_and_residual_graph_for_sets, UnGraph};
+use itertools::Itertools;
+use log::{debug, info, trace};
+use petgraph::data::DataMap;
+use petgraph::visit::{Data, EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
 
+use crate::cuts::compaction::Compaction;
+use crate::cuts::cut::{
+    generate_minimum_cut_closest_to_destination_with_mapping, Cut, ImportantCut,
+};
+use crate::cuts::flow::{augment_flow_between_sets, decompose_flow, vertices_reaching_destination};
+use crate::cuts::memo::{BranchMemo, BranchOutcome};
+use crate::cuts::minimum_cut::edge_capacities;
+use crate::cuts::path_residual::{get_augmenting_paths_and_residual_graph_for_sets, Path, UnGraph};
+use crate::cuts::verify::{
+    is_important_cut, is_important_cut_with_edges_in_use, reachable_vertices, validate_sets,
+};
+use crate::Error;
+
+/// Finds the important cuts of size at most k between the source and destination sets. The edges
+/// of each cut are sorted, and the cuts are guaranteed to be in ascending order of size, where cuts
+/// of the same size are ordered by their sorted edge indices. Panics if one of the sets is empty
+/// or the sets overlap, see [`try_important_cuts`].
 pub fn important_cuts<G>(
     original_graph: G,
     source_set: Vec<usize>,
     destination_set: Vec<usize>,
     k: usize,
-) -> Vec<ImportantCut>
+) -> Vec<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_important_cuts(original_graph, source_set, destination_set, k)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`important_cuts`], but returns an error instead of panicking if one of the sets is
+/// empty or the sets overlap.
+pub fn try_important_cuts<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Result<Vec<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    compacted_search(
+        original_graph,
+        source_set,
+        destination_set,
+        |_| 1,
+        branching(k),
+    )
+}
+
+/// Validates the sets and runs `search` on the compacted graph, in which each edge of the input
+/// graph is replaced by as many parallel edges as `edge_multiplicity` gives for its index. An edge
+/// without copies is left out, while an edge with more than k copies is never cut by a search for
+/// cuts of size at most k. The cuts that `search` returns are mapped back to the edges of the input
+/// graph and sorted like in [`important_cuts`].
+fn compacted_search<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    edge_multiplicity: impl Fn(usize) -> usize,
+    search: impl FnOnce(&UnGraph, Vec<usize>, Vec<usize>) -> Vec<ImportantCut>,
+) -> Result<Vec<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+
+    // the indices of the original graph may not be contiguous, so we work on a compacted graph
+    let (compacted_graph, compaction) = Compaction::new(
+        original_graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    let mut expanded_graph = UnGraph::with_capacity(compacted_graph.node_count(), 0);
+    for _ in 0..compacted_graph.node_count() {
+        expanded_graph.add_node(());
+    }
+    let mut expanded_to_compact = vec![];
+    for edge in compacted_graph.edge_references() {
+        let original = compaction.original_edges(&[edge.id().index()])[0];
+        for _ in 0..edge_multiplicity(original) {
+            expanded_graph.add_edge(edge.source(), edge.target(), ());
+            expanded_to_compact.push(edge.id().index());
+        }
+    }
+
+    let cuts = search(
+        &expanded_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+    );
+    info!("Found {} important cuts", cuts.len());
+
+    // an important cut is minimal, so it contains either all parallel copies of an edge or none
+    Ok(sort_cuts(
+        cuts.into_iter()
+            .map(|cut| {
+                let compact_edges = cut
+                    .edge_indices()
+                    .iter()
+                    .map(|&edge| expanded_to_compact[edge])
+                    .unique()
+                    .collect::<Vec<_>>();
+                ImportantCut::from(compaction.original_edges(&compact_edges))
+            })
+            .collect(),
+    ))
+}
+
+/// Search for [`compacted_search`] that branches on all edges of the graph it is given.
+fn branching(k: usize) -> impl FnOnce(&UnGraph, Vec<usize>, Vec<usize>) -> Vec<ImportantCut> {
+    move |graph, source_set, destination_set| {
+        let mut cuts = vec![];
+        important_cut_inner(
+            graph,
+            source_set,
+            destination_set,
+            k,
+            vec![true; graph.edge_count()],
+            vec![],
+            &mut cuts,
+        );
+        cuts
+    }
+}
+
+/// Sorts the cuts by their size and then by their edges. Cuts that are found in several branches
+/// are only kept once.
+pub(crate) fn sort_cuts(mut cuts: Vec<ImportantCut>) -> Vec<ImportantCut> {
+    cuts.sort();
+    cuts.dedup();
+    cuts
+}
+
+/// Branches on the edges of minimum cuts as in [`important_cuts`], where the graph has contiguous
+/// indices and only the edges marked in `edges_in_use` are present.
+pub(crate) fn important_cut_inner(
+    original_graph: &UnGraph,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    edges_in_use: Vec<bool>,
+    edges_in_cut: Vec<usize>,
+    important_cuts: &mut Vec<ImportantCut>,
+) {
+    let root = Branch {
+        source_set,
+        k,
+        edges_in_use,
+        edges_in_cut,
+        flow: None,
+        depth: 0,
+    };
+    search_from_root(original_graph, destination_set, root, None, important_cuts);
+}
+
+/// Same as [`important_cut_inner`] without edges in the cut, but the minimum cut of the first
+/// branch is found by augmenting the given flow value and flow of each edge, e.g. a maximum flow
+/// that is kept up to date while the graph changes, instead of an empty flow. The minimum cuts of
+/// the branches are looked up in and added to the memo. Returns the important cuts found.
+pub(crate) fn important_cuts_from_flow(
+    original_graph: &UnGraph,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    edges_in_use: Vec<bool>,
+    flow: (usize, Vec<isize>),
+    memo: &mut BranchMemo,
+) -> Vec<ImportantCut> {
+    let root = Branch {
+        source_set,
+        k,
+        edges_in_use,
+        edges_in_cut: vec![],
+        flow: Some(flow),
+        depth: 0,
+    };
+    let mut important_cuts = vec![];
+    search_from_root(
+        original_graph,
+        destination_set,
+        root,
+        Some(memo),
+        &mut important_cuts,
+    );
+    important_cuts
+}
+
+/// Explores the branches that follow from the root branch and collects the important cuts found
+/// in them.
+fn search_from_root(
+    original_graph: &UnGraph,
+    destination_set: Vec<usize>,
+    mut root: Branch,
+    mut memo: Option<&mut BranchMemo>,
+    important_cuts: &mut Vec<ImportantCut>,
+) {
+    // no cut can have more edges than the graph, so a larger k only makes the search longer
+    root.k = root
+        .k
+        .min(root.edges_in_use.iter().filter(|&&in_use| in_use).count());
+    let initial_source_set = root.source_set.clone();
+    let initial_edges_in_use = root.edges_in_use.clone();
+    let initial_edges_in_cut = root.edges_in_cut.clone();
+    let mut stack = vec![root];
+    let mut found = HashSet::new();
+    while let Some(branch) = stack.pop() {
+        let Some((cut, _)) = explore_branch(
+            original_graph,
+            &destination_set,
+            branch,
+            None,
+            memo.as_deref_mut(),
+            &mut stack,
+        ) else {
+            continue;
+        };
+        let cut = ImportantCut::from(cut.cut_edge_set);
+        if !found.insert(cut.clone()) {
+            continue;
+        }
+        // the branching may also find cuts that are not important, see ImportantCutsIter::report.
+        // The edges that were in the cut from the start are not part of the graph it is cut from
+        let new_edges = cut
+            .edge_indices()
+            .iter()
+            .copied()
+            .filter(|edge| !initial_edges_in_cut.contains(edge))
+            .collect::<Vec<_>>();
+        if is_important_cut_with_edges_in_use(
+            original_graph,
+            &initial_edges_in_use,
+            &initial_source_set,
+            &destination_set,
+            &new_edges,
+        ) {
+            important_cuts.push(cut);
+        }
+    }
+}
+
+/// A branch of the search for important cuts that is still to be explored, where `edges_in_cut`
+/// are the edges that have been chosen to be part of the cut so far and `depth` is the number of
+/// branchings that led to it. Only the first branch of a search may come with a `flow` to start
+/// the flow computation from.
+#[derive(Debug)]
+pub(crate) struct Branch {
+    source_set: Vec<usize>,
+    k: usize,
+    edges_in_use: Vec<bool>,
+    edges_in_cut: Vec<usize>,
+    flow: Option<(usize, Vec<isize>)>,
+    depth: usize,
+}
+
+/// Explores a single branch. Returns the cut found in it, whose sides are those of the minimum cut
+/// of the branch, together with the paths of that minimum cut, if any, and pushes the two branches
+/// that follow from it onto the stack so that the first one is explored next. No branches are
+/// pushed once `max_depth` has been reached. With a memo, the minimum cut of the branch is taken
+/// from it if possible, and stored in it otherwise.
+fn explore_branch(
+    original_graph: &UnGraph,
+    destination_set: &[usize],
+    branch: Branch,
+    max_depth: Option<usize>,
+    memo: Option<&mut BranchMemo>,
+    stack: &mut Vec<Branch>,
+) -> Option<(Cut, Vec<Path>)> {
+    let Branch {
+        source_set,
+        k,
+        edges_in_use,
+        edges_in_cut,
+        flow,
+        depth,
+    } = branch;
+    trace!(
+        "Branching with k = {} and {} source vertices",
+        k,
+        source_set.len()
+    );
+    // if the destination set cannot be reached at all, the empty cut is the only important cut
+    // and there is nothing to branch on
+    if depth == 0 {
+        let reachable = reachable_vertices(original_graph, &source_set, |edge| edges_in_use[edge]);
+        if !destination_set
+            .iter()
+            .any(|vertex| reachable.contains(vertex))
+        {
+            let (source_side, destination_side) = original_graph
+                .node_indices()
+                .map(|vertex| vertex.index())
+                .partition(|vertex| reachable.contains(vertex));
+            return Some((
+                Cut::new(source_side, destination_side, edges_in_cut),
+                vec![],
+            ));
+        }
+    }
+    // a destination vertex may have been added to the source set in an earlier branch
+    if source_set
+        .iter()
+        .any(|vertex| destination_set.contains(vertex))
+    {
+        return None;
+    }
+    let outcome = match memo {
+        Some(memo) => memo.get(&source_set, &edges_in_use, k).unwrap_or_else(|| {
+            let outcome = branch_minimum_cut(
+                original_graph,
+                destination_set,
+                &source_set,
+                &edges_in_use,
+                k,
+                flow,
+            );
+            memo.insert(&source_set, &edges_in_use, outcome.clone());
+            outcome
+        }),
+        None => branch_minimum_cut(
+            original_graph,
+            destination_set,
+            &source_set,
+            &edges_in_use,
+            k,
+            flow,
+        ),
+    };
+    let BranchOutcome::MinimumCut(min_cut, witness_paths) = outcome else {
+        return None;
+    };
+
+    // Report C u Z
+    let important_cut = Cut::new(
+        min_cut.source_set.clone(),
+        min_cut.destination_set.clone(),
+        [min_cut.cut_edge_set.clone(), edges_in_cut.clone()].concat(),
+    );
+
+    // return branch if k == 0 or if the min cut is of size k
+    if k == 0 || min_cut.size == k {
+        return Some((important_cut, witness_paths));
+    }
+
+    // stop branching if the search may not go any deeper
+    if max_depth.is_some_and(|max_depth| depth >= max_depth) {
+        return Some((important_cut, witness_paths));
+    }
+
+    // pick arbitrary edge from cut
+    let (edge, destination_side_vertex) = min_cut
+        .arbitrary_edge(original_graph)
+        .expect("A min cut should have an edge crossing it");
+
+    // branch into two cases, of which the second one is pushed first so that the first one is
+    // explored first
+
+    // 2. the arbitrary edge is part of an important cut
+
+    // in this case we disable the edge by marking it not in use anymore, and now that we've added
+    // an edge to an important cut, we reduce k by one
+    let mut new_edges_in_use = edges_in_use.clone();
+    new_edges_in_use[edge] = false;
+    stack.push(Branch {
+        source_set: min_cut.source_set.clone(),
+        k: k - 1,
+        edges_in_use: new_edges_in_use,
+        edges_in_cut: [edges_in_cut.clone(), vec![edge]].concat(),
+        flow: None,
+        depth: depth + 1,
+    });
+
+    // 1. the arbitrary edge is *not* part of an important cut
+
+    // the new source set is the source set of the min cut together with the destination side
+    // vertex of our chosen edge
+    stack.push(Branch {
+        source_set: [min_cut.source_set, vec![destination_side_vertex]].concat(),
+        k,
+        edges_in_use,
+        edges_in_cut,
+        flow: None,
+        depth: depth + 1,
+    });
+
+    Some((important_cut, witness_paths))
+}
+
+/// Computes the minimum cut closest to the destination set of a branch, starting from the given
+/// flow, if it has at most k edges.
+fn branch_minimum_cut(
+    original_graph: &UnGraph,
+    destination_set: &[usize],
+    source_set: &[usize],
+    edges_in_use: &[bool],
+    k: usize,
+    flow: Option<(usize, Vec<isize>)>,
+) -> BranchOutcome {
+    // the flow is pushed from the whole source set at once, so the sets are not contracted
+    let capacities = edges_in_use
+        .iter()
+        .map(|&in_use| usize::from(in_use))
+        .collect::<Vec<_>>();
+    let flow = flow.unwrap_or_else(|| (0, vec![0; original_graph.edge_count()]));
+    let (flow_value, edge_flows) = augment_flow_between_sets(
+        original_graph,
+        source_set,
+        destination_set,
+        &capacities,
+        k,
+        flow,
+    );
+    if flow_value == 0 {
+        return BranchOutcome::Disconnected;
+    }
+    if flow_value > k {
+        return BranchOutcome::LargerThan(k);
+    }
+    let witness_paths = decompose_flow(
+        original_graph,
+        source_set,
+        destination_set,
+        flow_value,
+        &mut edge_flows.clone(),
+    );
+    let in_destination_side =
+        vertices_reaching_destination(original_graph, destination_set, &capacities, &edge_flows);
+    let (destination_side, source_side) = original_graph
+        .node_indices()
+        .map(|vertex| vertex.index())
+        .partition(|&vertex| in_destination_side[vertex]);
+    // the edges removed in earlier branches are already in Z and are not part of the minimum cut
+    let cut_edges = original_graph
+        .edge_references()
+        .filter(|edge| {
+            edges_in_use[edge.id().index()]
+                && in_destination_side[edge.source().index()]
+                    != in_destination_side[edge.target().index()]
+        })
+        .map(|edge| edge.id().index())
+        .collect();
+    let min_cut = Cut::new(source_side, destination_side, cut_edges);
+    debug!("Found a min cut of size {}", min_cut.size);
+    BranchOutcome::MinimumCut(min_cut, witness_paths)
+}
+
+/// Lazily enumerates important cuts, see [`important_cuts_iter`]. Dropping the iterator stops the
+/// search, leaving the unexplored branches untouched.
+#[derive(Debug)]
+pub struct ImportantCutsIter {
+    graph: UnGraph,
+    compaction: Compaction,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    stack: Vec<Branch>,
+    reported: HashSet<ImportantCut>,
+    max_depth: Option<usize>,
+}
+
+impl ImportantCutsIter {
+    /// Maps the edges of a cut found in a branch to the input graph and returns the cut, unless
+    /// it has been found before or is not important. The branching finds every important cut, but
+    /// may also find cuts that are not, e.g. when an edge removed in a branch ends up with both
+    /// endpoints on the source side.
+    fn report(&mut self, cut_edges: &[usize]) -> Option<ImportantCut> {
+        let cut = ImportantCut::from(self.compaction.original_edges(cut_edges));
+        if !self.reported.insert(cut.clone()) {
+            return None;
+        }
+        is_important_cut(
+            &self.graph,
+            &self.source_set,
+            &self.destination_set,
+            cut_edges,
+        )
+        .then_some(cut)
+    }
+
+    /// Explores the next branch on the stack, or returns `None` if there are none left.
+    fn explore_next_branch(&mut self) -> Option<ExploredBranch> {
+        let branch = self.stack.pop()?;
+        let depth = branch.depth;
+        let cut = explore_branch(
+            &self.graph,
+            &self.destination_set,
+            branch,
+            self.max_depth,
+            None,
+            &mut self.stack,
+        );
+        let reported = cut
+            .as_ref()
+            .and_then(|(cut, _)| self.report(&cut.cut_edge_set));
+        Some(ExploredBranch {
+            depth,
+            cut,
+            reported,
+        })
+    }
+}
+
+/// What exploring a branch with [`ImportantCutsIter::explore_next_branch`] found: the cut of the
+/// branch and its paths in the compacted graph, if the flow was at most k, and the cut in the input
+/// graph if it is reported.
+struct ExploredBranch {
+    depth: usize,
+    cut: Option<(Cut, Vec<Path>)>,
+    reported: Option<ImportantCut>,
+}
+
+impl Iterator for ImportantCutsIter {
+    type Item = ImportantCut;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(explored) = self.explore_next_branch() {
+            if explored.reported.is_some() {
+                return explored.reported;
+            }
+        }
+        None
+    }
+}
+
+/// Same as [`important_cuts`], but the cuts are found one at a time while iterating, so that the
+/// search can be stopped early. The cuts are in the order in which they are found instead of being
+/// sorted. Panics if one of the sets is empty or the sets overlap, see [`try_important_cuts_iter`].
+pub fn important_cuts_iter<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> ImportantCutsIter
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_important_cuts_iter(original_graph, source_set, destination_set, k)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`important_cuts_iter`], but returns an error instead of panicking if one of the sets
+/// is empty or the sets overlap.
+pub fn try_important_cuts_iter<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Result<ImportantCutsIter, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+
+    let (compacted_graph, compaction) = Compaction::new(
+        original_graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    let source_set = compaction.compact_vertices(&source_set);
+    let initial_branch = Branch {
+        source_set: source_set.clone(),
+        k: k.min(compacted_graph.edge_count()),
+        edges_in_use: vec![true; compacted_graph.edge_count()],
+        edges_in_cut: vec![],
+        flow: None,
+        depth: 0,
+    };
+
+    Ok(ImportantCutsIter {
+        source_set,
+        destination_set: compaction.compact_vertices(&destination_set),
+        graph: compacted_graph,
+        compaction,
+        stack: vec![initial_branch],
+        reported: HashSet::new(),
+        max_depth: None,
+    })
+}
+
+/// Same as [`important_cuts`], but stops branching once `max_depth` branchings have been made, so
+/// that the search stays responsive on large inputs. With a limit, this is an approximation that
+/// may omit some important cuts, and the cut of the initial branch is the only one found with
+/// `Some(0)`. With `None`, all important cuts are found. Panics if one of the sets is empty or the
+/// sets overlap.
+pub fn important_cuts_with_max_depth<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    max_depth: Option<usize>,
+) -> Vec<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut iter = important_cuts_iter(original_graph, source_set, destination_set, k);
+    iter.max_depth = max_depth;
+    sort_cuts(iter.collect())
+}
+
+/// Same as [`important_cuts`], but the excluded edges are never cut, as if they were mandatory
+/// connections. They are always present, so no reported cut separates their endpoints. Panics if
+/// one of the sets is empty, the sets overlap or an excluded edge does not exist, see
+/// [`try_important_cuts_with_excluded_edges`].
+pub fn important_cuts_with_excluded_edges<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    excluded_edges: &[usize],
+) -> Vec<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_important_cuts_with_excluded_edges(
+        original_graph,
+        source_set,
+        destination_set,
+        k,
+        excluded_edges,
+    )
+    .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`important_cuts_with_excluded_edges`], but returns an error instead of panicking if
+/// one of the sets is empty, the sets overlap or an excluded edge does not exist.
+pub fn try_important_cuts_with_excluded_edges<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    excluded_edges: &[usize],
+) -> Result<Vec<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let edges = edge_set(original_graph);
+    if let Some(&edge) = excluded_edges.iter().find(|edge| !edges.contains(edge)) {
+        return Err(Error::MissingEdge(edge));
+    }
+    // no cut can have more edges than the graph, and k + 1 parallel copies make an excluded edge
+    // more expensive than any cut of size at most k, so it is left out of every cut
+    let k = k.min(edges.len());
+    compacted_search(
+        original_graph,
+        source_set,
+        destination_set,
+        |edge| {
+            if excluded_edges.contains(&edge) {
+                k + 1
+            } else {
+                1
+            }
+        },
+        branching(k),
+    )
+}
+
+/// Same as [`important_cuts`], but the forced edges are part of every reported cut, as if they
+/// were already broken. They are removed from the graph before the search, like an edge that has
+/// been branched into the cut, and count towards k. Returns no cuts if there are more forced edges
+/// than k. Panics if one of the sets is empty, the sets overlap or a forced edge does not exist,
+/// see [`try_important_cuts_with_forced_edges`].
+pub fn important_cuts_with_forced_edges<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    forced_cut_edges: &[usize],
+) -> Vec<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_important_cuts_with_forced_edges(
+        original_graph,
+        source_set,
+        destination_set,
+        k,
+        forced_cut_edges,
+    )
+    .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`important_cuts_with_forced_edges`], but returns an error instead of panicking if one
+/// of the sets is empty, the sets overlap or a forced edge does not exist.
+pub fn try_important_cuts_with_forced_edges<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    forced_cut_edges: &[usize],
+) -> Result<Vec<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let edges = edge_set(original_graph);
+    if let Some(&edge) = forced_cut_edges.iter().find(|edge| !edges.contains(edge)) {
+        return Err(Error::MissingEdge(edge));
+    }
+    let forced_cut_edges = forced_cut_edges
+        .iter()
+        .copied()
+        .unique()
+        .collect::<Vec<_>>();
+    if forced_cut_edges.len() > k {
+        return Ok(vec![]);
+    }
+
+    let cuts = compacted_search(
+        original_graph,
+        source_set,
+        destination_set,
+        |edge| usize::from(!forced_cut_edges.contains(&edge)),
+        branching(k - forced_cut_edges.len()),
+    )?;
+    // a cut of the graph without the forced edges is important in it, so the forced edges do not
+    // change which cuts are reported
+    Ok(sort_cuts(
+        cuts.into_iter()
+            .map(|cut| {
+                ImportantCut::from([cut.into_edge_indices(), forced_cut_edges.clone()].concat())
+            })
+            .collect(),
+    ))
+}
+
+/// Same as [`important_cuts`], but the size of a cut is the total capacity of its edges, which
+/// `capacity_fn` reads from the payload of each edge, e.g. `|&weight| weight as usize` for a
+/// `Graph<(), u32>`. Each edge is replaced by as many parallel edges as its capacity, so edges of
+/// capacity zero are never cut. An edge of capacity above k never fits in a cut, so it gets only
+/// k + 1 copies. Panics if one of the sets is empty or the sets overlap, see
+/// [`try_important_cuts_with_capacities`].
+pub fn important_cuts_with_capacities<G, F>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    capacity_fn: F,
+) -> Vec<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(&G::EdgeWeight) -> usize,
+{
+    try_important_cuts_with_capacities(original_graph, source_set, destination_set, k, capacity_fn)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`important_cuts_with_capacities`], but returns an error instead of panicking if one
+/// of the sets is empty or the sets overlap.
+pub fn try_important_cuts_with_capacities<G, F>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    capacity_fn: F,
+) -> Result<Vec<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(&G::EdgeWeight) -> usize,
+{
+    let capacities = edge_capacities(original_graph, capacity_fn);
+    // like an excluded edge, k + 1 copies are enough to keep the edge out of every cut
+    compacted_search(
+        original_graph,
+        source_set,
+        destination_set,
+        |edge| capacities[edge].min(k.saturating_add(1)),
+        branching(k),
+    )
+}
+
+/// Same as [`important_cuts`], but only in the subgraph induced by the vertices for which
+/// `node_filter` returns true. The other vertices and their edges are ignored, so they are never
+/// part of a path or a cut. Panics if one of the sets is empty, the sets overlap or a vertex of
+/// the sets does not pass the filter, see [`try_important_cuts_with_node_filter`].
+pub fn important_cuts_with_node_filter<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    node_filter: impl Fn(usize) -> bool,
+) -> Vec<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_important_cuts_with_node_filter(original_graph, source_set, destination_set, k, node_filter)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`important_cuts_with_node_filter`], but returns an error instead of panicking if one
+/// of the sets is empty, the sets overlap or a vertex of the sets does not pass the filter.
+pub fn try_important_cuts_with_node_filter<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    node_filter: impl Fn(usize) -> bool,
+) -> Result<Vec<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    if let Some(&vertex) = source_set
+        .iter()
+        .chain(&destination_set)
+        .find(|&&vertex| !node_filter(vertex))
+    {
+        return Err(Error::FilteredVertex(vertex));
+    }
+    // the edges of excluded vertices are left out like the edges removed while branching
+    let mut edges_in_use = vec![false; original_graph.edge_bound()];
+    for edge in original_graph.edge_references() {
+        edges_in_use[EdgeIndexable::to_index(&original_graph, edge.id())] =
+            node_filter(NodeIndexable::to_index(&original_graph, edge.source()))
+                && node_filter(NodeIndexable::to_index(&original_graph, edge.target()));
+    }
+    compacted_search(
+        original_graph,
+        source_set,
+        destination_set,
+        |edge| usize::from(edges_in_use[edge]),
+        branching(k),
+    )
+}
+
+/// Indices of the edges of the graph.
+fn edge_set<G>(graph: G) -> HashSet<usize>
+where
+    G: EdgeIndexable + IntoEdgeReferences,
+{
+    graph
+        .edge_references()
+        .map(|edge| EdgeIndexable::to_index(&graph, edge.id()))
+        .collect()
+}
+
+/// Same as [`important_cuts`], but each cut comes with the edge-disjoint paths that witness the
+/// minimum cut it was found from. Every edge of that minimum cut lies on one of the paths, while
+/// the other edges of the cut were removed in the branch before the paths were found. The paths
+/// start from the source side of the branch, which may have grown beyond the source set. If a cut
+/// is found in several branches, the paths of the first branch are returned.
+pub fn important_cuts_with_paths<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Vec<(ImportantCut, Vec<Path>)>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut iter = important_cuts_iter(original_graph, source_set, destination_set, k);
+    let mut cuts = vec![];
+    while let Some(explored) = iter.explore_next_branch() {
+        if let (Some((_, paths)), Some(cut)) = (explored.cut, explored.reported) {
+            let paths = paths
+                .into_iter()
+                .map(|path| Path {
+                    vertices: iter.compaction.original_vertices(&path.vertices),
+                    edges: iter.compaction.original_edges(&path.edges),
+                })
+                .collect();
+            cuts.push((cut, paths));
+        }
+    }
+    cuts.sort_by(|(a, _), (b, _)| a.cmp(b));
+    cuts
+}
+
+/// Same as [`important_cuts`], but returns each cut with its source side and destination side,
+/// which are those of the minimum cut the cut was found from. The sides and the edges of each cut
+/// are sorted, and the cuts are ordered like in [`important_cuts`].
+pub fn important_cuts_full<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Vec<Cut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut iter = important_cuts_iter(original_graph, source_set, destination_set, k);
+    let mut cuts = vec![];
+    while let Some(explored) = iter.explore_next_branch() {
+        if let (Some((cut, _)), Some(important_cut)) = (explored.cut, explored.reported) {
+            let mut cut_edge_set = important_cut.into_edge_indices();
+            let mut source_side = iter.compaction.original_vertices(&cut.source_set);
+            let mut destination_side = iter.compaction.original_vertices(&cut.destination_set);
+            source_side.sort();
+            destination_side.sort();
+            cut_edge_set.sort();
+            cuts.push(Cut::new(source_side, destination_side, cut_edge_set));
+        }
+    }
+    cuts.sort_by(|a, b| {
+        a.size
+            .cmp(&b.size)
+            .then_with(|| a.cut_edge_set.cmp(&b.cut_edge_set))
+    });
+    cuts
+}
+
+/// Same as [`important_cuts_iter`], but each cut is sent through the channel as soon as it is
+/// found, so that the cuts can be consumed on another thread. Returns once all cuts have been sent,
+/// or as soon as the receiving end has been dropped.
+pub fn important_cuts_into_channel<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    tx: Sender<ImportantCut>,
+) where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    for cut in important_cuts_iter(original_graph, source_set, destination_set, k) {
+        if tx.send(cut).is_err() {
+            return;
+        }
+    }
+}
+
+/// Finds the important cut of size at most k with the fewest edges, which is the minimum cut
+/// closest to the destination set, so that only a single flow has to be computed. Returns `None`
+/// if the minimum cut is larger than k, and the empty cut if the sets are not connected. Panics if
+/// one of the sets is empty or the sets overlap, see [`try_smallest_important_cut`].
+pub fn smallest_important_cut<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Option<ImportantCut>
 where
-    G: NodeIndexable + IntoEdgeReferences,
-{
-    fn important_cut_inner(
-        original_graph: &UnGraph,
-        source_set: Vec<usize>,
-        destination_set: Vec<usize>,
-        k: usize,
-        edges_in_use: Vec<bool>,
-        edges_in_cut: Vec<usize>,
-        important_cuts: &mut Vec<ImportantCut>,
-    ) {
-        match get_augmenting_paths_and_residual_graph_for_sets(
-            &original_graph,
-            source_set,
-            destination_set.clone(),
-            k,
-            &edges_in_use,
-        ) {
-            Some((paths, residual, index_mapping)) => {
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_smallest_important_cut(original_graph, source_set, destination_set, k)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`smallest_important_cut`], but returns an error instead of panicking if one of the
+/// sets is empty or the sets overlap.
+pub fn try_smallest_important_cut<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Result<Option<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut cuts = compacted_search(
+        original_graph,
+        source_set,
+        destination_set,
+        |_| 1,
+        |graph, source_set, destination_set| {
+            // like in the search, sets that are not connected are separated by the empty cut
+            let reachable = reachable_vertices(graph, &source_set, |_| true);
+            if !destination_set
+                .iter()
+                .any(|vertex| reachable.contains(vertex))
+            {
+                return vec![ImportantCut::from(vec![])];
+            }
+            get_augmenting_paths_and_residual_graph_for_sets(
+                graph,
+                source_set,
+                destination_set,
+                k,
+                &vec![true; graph.edge_count()],
+            )
+            .map(|(paths, residual, index_mapping)| {
                 let min_cut = generate_minimum_cut_closest_to_destination_with_mapping(
                     &paths,
                     residual,
                     index_mapping,
-                );
-
-                // Report C u Z
-                important_cuts.push(ImportantCut::from(
-                    [min_cut.cut_edge_set.clone(), edges_in_cut.clone()].concat(),
-                ));
+                )
+                .expect("The index mapping should belong to the contracted graph");
+                ImportantCut::from(min_cut.cut_edge_set)
+            })
+            .into_iter()
+            .collect()
+        },
+    )?;
+    Ok(cuts.pop())
+}
 
-                // return branch if k == 0 or if the min cut is of size k
-                if k == 0 || min_cut.size == k {
-                    return;
-                }
+/// Finds the important cut of size at most k whose edges have the smallest total cost, where
+/// `cost` gives the cost of an edge by its index. Of cuts with the same cost, the smallest one is
+/// returned. Returns `None` if there are no important cuts. Panics if one of the sets is empty, the
+/// sets overlap or the cost of a cut overflows, see [`try_min_cost_important_cut`].
+pub fn min_cost_important_cut<G, F>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    cost: F,
+) -> Option<ImportantCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(usize) -> u64,
+{
+    try_min_cost_important_cut(original_graph, source_set, destination_set, k, cost)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
 
-                // pick arbitrary edge from cut
-                let (edge, destination_side_vertex) = min_cut.arbitrary_edge(&original_graph);
+/// Same as [`min_cost_important_cut`], but returns an error instead of panicking if one of the
+/// sets is empty, the sets overlap or the cost of a cut overflows.
+pub fn try_min_cost_important_cut<G, F>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+    cost: F,
+) -> Result<Option<ImportantCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(usize) -> u64,
+{
+    // the cuts are sorted by their size, and the first cut of minimum cost is kept
+    let mut cheapest: Option<(u64, ImportantCut)> = None;
+    for cut in try_important_cuts(original_graph, source_set, destination_set, k)? {
+        let cut_cost = cut.try_weight(&cost)?;
+        if cheapest
+            .as_ref()
+            .is_none_or(|(cheapest_cost, _)| cut_cost < *cheapest_cost)
+        {
+            cheapest = Some((cut_cost, cut));
+        }
+    }
+    Ok(cheapest.map(|(_, cut)| cut))
+}
 
-                // branch into two cases
-                // 1. the arbitrary edge is *not* part of an important cut
+/// How much work the search for important cuts did, see [`important_cuts_with_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CutStats {
+    /// Number of branches whose flow was computed, including those where it was larger than k
+    pub branches_explored: usize,
+    /// Number of branches whose flow was at most k, so that their minimum cut was computed
+    pub min_cut_computations: usize,
+    /// Number of distinct important cuts found
+    pub cuts_reported: usize,
+    /// Largest number of branchings that led to an explored branch
+    pub max_depth: usize,
+}
 
-                // the new source set is the source set of the min cut together with the destination
-                // side vertex of our chosen edge
-                important_cut_inner(
-                    &original_graph,
-                    [min_cut.source_set.clone(), vec![destination_side_vertex]].concat(),
-                    destination_set.clone(),
-                    k,
-                    edges_in_use.clone(),
-                    edges_in_cut.clone(),
-                    important_cuts,
-                );
-
-                // 2. the arbitrary edge is part of an important cut
-
-                // in this case we disable the edge by marking it not in use anymore
-                let mut new_edges_in_use = edges_in_use.clone();
-                new_edges_in_use[edge] = false;
-
-                // the new source is the source set of the min cut, and now that we've added an edge
-                // to an important cut, we reduce k by one
-                important_cut_inner(
-                    &original_graph,
-                    min_cut.source_set,
-                    destination_set.clone(),
-                    k - 1,
-                    new_edges_in_use,
-                    [edges_in_cut, vec![edge]].concat(),
-                    important_cuts,
-                );
-            }
-            None => {
-                // no more augmenting paths
-                return;
-            }
+/// Same as [`important_cuts`], but also returns statistics of the search, e.g. to see how the
+/// number of branches grows with k on a given input.
+pub fn important_cuts_with_stats<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> (Vec<ImportantCut>, CutStats)
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut iter = important_cuts_iter(original_graph, source_set, destination_set, k);
+    let mut stats = CutStats::default();
+    let mut cuts = vec![];
+    while let Some(explored) = iter.explore_next_branch() {
+        stats.branches_explored += 1;
+        stats.max_depth = stats.max_depth.max(explored.depth);
+        if explored.cut.is_some() {
+            stats.min_cut_computations += 1;
         }
+        cuts.extend(explored.reported);
     }
+    stats.cuts_reported = cuts.len();
+    (sort_cuts(cuts), stats)
+}
 
-    let original_graph_edges = original_graph.edge_references().map(|edge| {
-        let source_index = NodeIndexable::to_index(&original_graph, edge.source());
-        let target_index = NodeIndexable::to_index(&original_graph, edge.target());
-        (source_index, target_index)
-    });
+/// Sizes of the important cuts of size at most k, which are found like in [`important_cuts`] but
+/// without keeping the cuts.
+pub fn important_cut_sizes<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> BTreeSet<usize>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut iter = important_cuts_iter(original_graph, source_set, destination_set, k);
+    let mut sizes = BTreeSet::new();
+    while let Some(explored) = iter.explore_next_branch() {
+        if let Some(cut) = explored.reported {
+            sizes.insert(cut.edge_indices().len());
+        }
+    }
+    sizes
+}
 
-    let original_graph_as_un_graph = UnGraph::from_edges(original_graph_edges);
+/// Same as [`important_cuts`], but the cuts are grouped by their size.
+pub fn important_cuts_by_size<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> BTreeMap<usize, Vec<ImportantCut>>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut cuts_by_size = BTreeMap::<usize, Vec<ImportantCut>>::new();
+    for cut in important_cuts(original_graph, source_set, destination_set, k) {
+        cuts_by_size
+            .entry(cut.edge_indices().len())
+            .or_default()
+            .push(cut);
+    }
+    cuts_by_size
+}
 
-    let mut cuts = vec![];
-    let initial_edges_in_use = vec![true; original_graph_as_un_graph.edge_count()];
+/// Same as [`important_cuts`], but also returns the source side and the destination side of each
+/// cut. The source side consists of the vertices that can be reached from the source set once the
+/// cut edges are removed, and the destination side of all other vertices that are incident to an
+/// edge or in one of the sets. Both sides are sorted.
+pub fn important_cuts_with_sides<G>(
+    original_graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Vec<(ImportantCut, Vec<usize>, Vec<usize>)>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let vertices = original_graph
+        .edge_references()
+        .flat_map(|edge| {
+            [
+                NodeIndexable::to_index(&original_graph, edge.source()),
+                NodeIndexable::to_index(&original_graph, edge.target()),
+            ]
+        })
+        .chain(source_set.iter().copied())
+        .chain(destination_set.iter().copied())
+        .collect::<BTreeSet<_>>();
 
-    important_cut_inner(
-        &original_graph_as_un_graph,
-        source_set,
-        destination_set,
-        k,
-        initial_edges_in_use,
-        vec![],
-        &mut cuts,
-    );
+    important_cuts(original_graph, source_set.clone(), destination_set, k)
+        .into_iter()
+        .map(|cut| {
+            let reachable = reachable_vertices(original_graph, &source_set, |edge| {
+                !cut.edge_indices().contains(&edge)
+            });
+            let (source_side, destination_side): (Vec<usize>, Vec<usize>) = vertices
+                .iter()
+                .partition(|vertex| reachable.contains(vertex));
+            (cut, source_side, destination_side)
+        })
+        .collect()
+}
 
-    cuts
+/// Same as [`important_cuts`], but also returns the payloads of the edges in each important cut,
+/// e.g. to report their weights. The payloads are in the same order as the edge indices of the cut.
+pub fn important_cuts_with_payloads<'a, N, E, G>(
+    graph: &'a G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Vec<(ImportantCut, Vec<&'a E>)>
+where
+    G: Data<NodeWeight = N, EdgeWeight = E> + DataMap + EdgeIndexable,
+    &'a G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    important_cuts(graph, source_set, destination_set, k)
+        .into_iter()
+        .map(|cut| {
+            let payloads = cut
+                .edge_indices()
+                .iter()
+                .map(|&edge_index| {
+                    let edge = EdgeIndexable::from_index(graph, edge_index);
+                    match graph.edge_weight(edge) {
+                        None => panic!("Edge {} does not exist in graph", edge_index),
+                        Some(payload) => payload,
+                    }
+                })
+                .collect();
+            (cut, payloads)
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::cuts::cut::ImportantCut;
-    use crate::cuts::important_cut::important_cuts;
+    use crate::cuts::conversion::{to_directed, to_undirected};
+    use crate::cuts::cut::{Cut, ImportantCut};
+    use crate::cuts::important_cut::{
+        important_cut_sizes, important_cuts, important_cuts_by_size, important_cuts_full,
+        important_cuts_into_channel, important_cuts_iter, important_cuts_with_capacities,
+        important_cuts_with_excluded_edges, important_cuts_with_forced_edges,
+        important_cuts_with_max_depth, important_cuts_with_node_filter, important_cuts_with_paths,
+        important_cuts_with_payloads, important_cuts_with_sides, important_cuts_with_stats,
+        min_cost_important_cut, smallest_important_cut, try_important_cuts,
+        try_important_cuts_iter, try_important_cuts_with_capacities,
+        try_important_cuts_with_excluded_edges, try_important_cuts_with_forced_edges,
+        try_important_cuts_with_node_filter, try_min_cost_important_cut,
+        try_smallest_important_cut, CutStats,
+    };
+    use crate::cuts::minimum_cut::{try_max_flow_between_sets, try_minimum_cut};
+    use crate::cuts::naive::{filter_important_cuts, generate_cuts_for_sets};
     use crate::cuts::path_residual::UnGraph;
+    use crate::Error;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use petgraph::stable_graph::{NodeIndex, StableUnGraph};
+    use petgraph::{Graph, Undirected};
+    use proptest::collection::vec;
+    use proptest::option;
+    use proptest::prelude::*;
+    use proptest::sample::Index;
+    use std::collections::BTreeSet;
+    use std::sync::{mpsc, Mutex};
+    use std::thread;
 
     #[test]
     fn simple_line() {
-        let graph = UnGraph::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
         let source = vec![0];
         let destination = vec![4];
         let k = 1;
 
-        important_cuts(&graph, source, destination, k)
-            .iter()
-            .for_each(|imp_cut| {
-                assert_eq!(1, imp_cut.edge_indices.len());
-                assert_eq!(3, imp_cut.edge_indices[0]);
-                assert_eq!((3, 4), imp_cut.vertex_pairs(&graph)[0]);
-            });
+        let result = important_cuts(&graph, source, destination, k);
+        assert_eq!(1, result.len());
+        assert_eq!(vec![3], result[0].edge_indices());
+        assert_eq!(vec![(3, 4)], result[0].vertex_pairs(&graph));
     }
 
-    fn all_contained(lhs: Vec<usize>, rhs: Vec<usize>) -> bool {
-        lhs.iter().all(|elem| rhs.contains(elem))
+    #[test]
+    fn simple_line_full() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+        let result = important_cuts_full(&graph, vec![0], vec![4], 1);
+        assert_eq!(1, result.len());
+        assert_eq!(vec![0, 1, 2, 3], result[0].source_set);
+        assert_eq!(vec![4], result[0].destination_set);
+        assert_eq!(vec![3], result[0].cut_edge_set);
+        assert_eq!(1, result[0].size);
     }
 
-    fn all_contained_vec(lhs: Vec<Vec<usize>>, rhs: Vec<Vec<usize>>) -> bool {
-        lhs.iter().all(|lhs_elem| {
-            rhs.iter()
-                .find(|&rhs_elem| all_contained(lhs_elem.clone(), rhs_elem.clone()))
-                .is_some()
-        })
+    #[test]
+    fn simple_line_with_sides() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+        let result = important_cuts_with_sides(&graph, vec![0], vec![4], 1);
+        assert_eq!(1, result.len());
+        let (imp_cut, source_side, destination_side) = &result[0];
+        assert_eq!(&vec![3], imp_cut.edge_indices());
+        assert_eq!(&vec![0, 1, 2, 3], source_side);
+        assert_eq!(&vec![4], destination_side);
+    }
+
+    #[test]
+    fn simple_line_with_paths() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+        let result = important_cuts_with_paths(&graph, vec![0], vec![4], 1);
+        assert_eq!(1, result.len());
+        let (imp_cut, paths) = &result[0];
+        assert_eq!(&vec![3], imp_cut.edge_indices());
+        assert_eq!(1, paths.len());
+        assert_eq!(vec![0, 1, 2, 3, 4], paths[0].vertices);
+        assert_eq!(vec![0, 1, 2, 3], paths[0].edges);
     }
 
     #[test]
     fn simple_y_shape() {
-        let graph = UnGraph::from_edges(&[(0, 1), (1, 2), (1, 3)]);
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3)]);
         let source = vec![0];
         let destination = vec![2, 3];
 
@@ -160,7 +1278,7 @@ mod tests {
         let result_1_edges = ImportantCut::vec_edge_indices(result_1);
 
         let expected_important_cuts_1 = vec![vec![0]];
-        assert!(all_contained_vec(expected_important_cuts_1, result_1_edges));
+        assert_eq!(expected_important_cuts_1, result_1_edges);
 
         // for k = 2
         let k2 = 2;
@@ -169,24 +1287,149 @@ mod tests {
         let result_2_edges = ImportantCut::vec_edge_indices(result_2);
 
         let expected_important_cuts_2 = vec![vec![0], vec![1, 2]];
-        assert!(all_contained_vec(expected_important_cuts_2, result_2_edges));
+        assert_eq!(expected_important_cuts_2, result_2_edges);
+    }
+
+    /// Logger that keeps the records in memory, so that tests can inspect them.
+    struct CapturingLogger {
+        records: Mutex<Vec<(Level, String)>>,
+    }
+
+    impl Log for CapturingLogger {
+        fn enabled(&self, _metadata: &Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &Record) {
+            self.records
+                .lock()
+                .unwrap()
+                .push((record.level(), record.args().to_string()));
+        }
+
+        fn flush(&self) {}
     }
 
+    static LOGGER: CapturingLogger = CapturingLogger {
+        records: Mutex::new(vec![]),
+    };
+
     #[test]
-    fn simple_binary_tree() {
-        fn create_binary_tree(levels: usize) -> UnGraph {
-            assert!(levels > 0);
-            let mut edges = vec![];
-            let total_nodes_with_children = (2 << (levels - 2)) - 1;
-            for i in 0..total_nodes_with_children {
-                let left_child = 2 * i + 1;
-                let right_child = 2 * i + 2;
-                edges.push((i, left_child));
-                edges.push((i, right_child));
-            }
-            UnGraph::from_edges(edges)
+    fn smallest_cut_of_y_shape() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        let smallest = smallest_important_cut(&graph, vec![0], vec![2, 3], 2).unwrap();
+        assert_eq!(vec![0], smallest.edge_indices());
+        assert_eq!(important_cuts(&graph, vec![0], vec![2, 3], 2)[0], smallest);
+
+        // the only cut, (1, 2) and (1, 3), is larger than k
+        assert_eq!(
+            None,
+            smallest_important_cut(&graph, vec![0, 1], vec![2, 3], 1)
+        );
+    }
+
+    #[test]
+    fn smallest_cut_of_disconnected_sets_is_empty() {
+        let graph = UnGraph::from_edges([(0, 1), (2, 3)]);
+
+        let smallest = smallest_important_cut(&graph, vec![0], vec![3], 1);
+        assert_eq!(Some(ImportantCut::from(vec![])), smallest);
+        assert_eq!(
+            important_cuts(&graph, vec![0], vec![3], 1),
+            vec![smallest.unwrap()]
+        );
+    }
+
+    #[test]
+    fn min_cuts_are_logged() {
+        // the logger can only be installed once, so ignore the error if it already is
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(LevelFilter::Trace);
+
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3)]);
+        important_cuts(&graph, vec![0], vec![2, 3], 2);
+
+        let records = LOGGER.records.lock().unwrap();
+        assert!(records
+            .iter()
+            .any(|(level, message)| *level == Level::Debug && message.contains("min cut")));
+    }
+
+    #[test]
+    fn stats_of_y_shape() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        let (cuts, stats) = important_cuts_with_stats(&graph, vec![0], vec![2, 3], 2);
+        assert_eq!(important_cuts(&graph, vec![0], vec![2, 3], 2), cuts);
+        // the branch without the edge (0, 1) has no flow left, so no minimum cut is computed
+        assert_eq!(
+            CutStats {
+                branches_explored: 3,
+                min_cut_computations: 2,
+                cuts_reported: 2,
+                max_depth: 1,
+            },
+            stats
+        );
+    }
+
+    #[test]
+    fn y_shape_cuts_by_size() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        let cuts_by_size = important_cuts_by_size(&graph, vec![0], vec![2, 3], 2)
+            .into_iter()
+            .map(|(size, cuts)| (size, ImportantCut::vec_edge_indices(cuts)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(1, vec![vec![0]]), (2, vec![vec![1, 2]])],
+            cuts_by_size
+        );
+    }
+
+    #[test]
+    fn cheapest_of_equal_size_cuts() {
+        //     1 - 2
+        //    /  \
+        //   0    3
+        //    \
+        //     4 - 5
+        //      \
+        //       6
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3), (0, 4), (4, 5), (4, 6)]);
+        let costs = [10, 1, 1, 1, 5, 5];
+
+        let cuts =
+            ImportantCut::vec_edge_indices(important_cuts(&graph, vec![0], vec![2, 3, 5, 6], 3));
+        assert_eq!(vec![vec![0, 3], vec![0, 4, 5], vec![1, 2, 3]], cuts);
+
+        let cheapest =
+            min_cost_important_cut(&graph, vec![0], vec![2, 3, 5, 6], 3, |edge| costs[edge]);
+        assert_eq!(vec![1, 2, 3], cheapest.unwrap().edge_indices());
+
+        let costs = [u64::MAX, 1, 1, 1, 5, 5];
+        assert_eq!(
+            Err(Error::Overflow),
+            try_min_cost_important_cut(&graph, vec![0], vec![2, 3, 5, 6], 3, |edge| costs[edge])
+        );
+    }
+
+    fn create_binary_tree(levels: usize) -> UnGraph {
+        assert!(levels > 0);
+        let mut edges = vec![];
+        let total_nodes_with_children = (2 << (levels - 2)) - 1;
+        for i in 0..total_nodes_with_children {
+            let left_child = 2 * i + 1;
+            let right_child = 2 * i + 2;
+            edges.push((i, left_child));
+            edges.push((i, right_child));
         }
+        UnGraph::from_edges(edges)
+    }
 
+    #[test]
+    fn simple_binary_tree() {
         let graph = create_binary_tree(3);
         let source = vec![0];
         let destination = (3..=6).collect();
@@ -195,7 +1438,493 @@ mod tests {
         let result = important_cuts(&graph, source, destination, k);
         let result_edges = ImportantCut::vec_edge_indices(result);
 
-        let expected_important_cuts = vec![vec![0, 4, 5], vec![2, 3, 1]];
-        assert!(all_contained_vec(expected_important_cuts, result_edges));
+        let expected_important_cuts = vec![vec![0, 1], vec![0, 4, 5], vec![1, 2, 3]];
+        assert_eq!(expected_important_cuts, result_edges);
+    }
+
+    #[test]
+    fn binary_tree_cut_sizes() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+
+        assert!(important_cut_sizes(&graph, vec![0], destination.clone(), 1).is_empty());
+        assert_eq!(
+            BTreeSet::from([2]),
+            important_cut_sizes(&graph, vec![0], destination.clone(), 2)
+        );
+        assert_eq!(
+            BTreeSet::from([2, 3]),
+            important_cut_sizes(&graph, vec![0], destination, 3)
+        );
+    }
+
+    #[test]
+    fn k_of_at_least_edge_count_finds_all_cuts() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+        let all_cuts = vec![vec![0, 1], vec![0, 4, 5], vec![1, 2, 3], vec![2, 3, 4, 5]];
+
+        for k in [graph.edge_count(), graph.edge_count() + 1, usize::MAX] {
+            let result = important_cuts(&graph, vec![0], destination.clone(), k);
+            assert_eq!(all_cuts, ImportantCut::vec_edge_indices(result));
+            let result = important_cuts_iter(&graph, vec![0], destination.clone(), k)
+                .map(ImportantCut::into_edge_indices)
+                .collect::<BTreeSet<_>>();
+            assert_eq!(BTreeSet::from_iter(all_cuts.clone()), result);
+        }
+    }
+
+    #[test]
+    fn excluded_edge_is_never_cut() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+
+        let result = important_cuts_with_excluded_edges(&graph, vec![0], destination, 4, &[0]);
+        assert_eq!(
+            vec![vec![1, 2, 3], vec![2, 3, 4, 5]],
+            ImportantCut::vec_edge_indices(result)
+        );
+    }
+
+    #[test]
+    fn forced_edge_is_in_every_cut() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+
+        let result =
+            important_cuts_with_forced_edges(&graph, vec![0], destination.clone(), 4, &[0]);
+        assert_eq!(
+            vec![vec![0, 1], vec![0, 4, 5]],
+            ImportantCut::vec_edge_indices(result)
+        );
+        assert!(
+            important_cuts_with_forced_edges(&graph, vec![0], destination, 1, &[0, 2]).is_empty()
+        );
+    }
+
+    #[cfg(feature = "deterministic")]
+    #[test]
+    fn repeated_searches_branch_the_same_way() {
+        //    -1-          9---6
+        //   / | \         |  /
+        //  /  |  \        | /
+        // 0---2---4---7---10
+        //  \  |          /
+        //   \ |         /
+        //    -3---5---8-
+        let edges = [
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 4),
+            (3, 5),
+            (4, 7),
+            (5, 8),
+            (7, 10),
+            (8, 10),
+            (6, 10),
+            (6, 9),
+            (9, 10),
+        ];
+        // edge indices change with the order of the edges, so the cuts are compared by endpoints
+        let search = |edges: &[(usize, usize)]| {
+            let graph = UnGraph::from_edges(edges);
+            let endpoints = |cut_edges: &[usize]| {
+                let mut pairs = cut_edges
+                    .iter()
+                    .map(|&edge| {
+                        (
+                            edges[edge].0.min(edges[edge].1),
+                            edges[edge].0.max(edges[edge].1),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                pairs.sort();
+                pairs
+            };
+            let mut cuts = important_cuts_full(&graph, vec![6], vec![0], 4)
+                .into_iter()
+                .map(|cut| {
+                    (
+                        endpoints(&cut.cut_edge_set),
+                        cut.source_set,
+                        cut.destination_set,
+                    )
+                })
+                .collect::<Vec<_>>();
+            cuts.sort();
+            let mut paths = important_cuts_with_paths(&graph, vec![6], vec![0], 4)
+                .into_iter()
+                .map(|(cut, paths)| {
+                    let vertices = paths
+                        .into_iter()
+                        .map(|path| path.vertices)
+                        .collect::<Vec<_>>();
+                    (endpoints(cut.edge_indices()), vertices)
+                })
+                .collect::<Vec<_>>();
+            paths.sort();
+            let (_, stats) = important_cuts_with_stats(&graph, vec![6], vec![0], 4);
+            (cuts, paths, stats)
+        };
+
+        let expected = search(&edges);
+        assert_eq!(2, expected.0.len());
+        for _ in 0..5 {
+            assert_eq!(expected, search(&edges));
+        }
+        // the compacted indices of the vertices follow the order of the edges, so the branching
+        // may differ, but the cuts and their sides do not
+        let mut shuffled = edges;
+        shuffled.reverse();
+        shuffled.swap(0, 7);
+        assert_eq!(expected.0, search(&shuffled).0);
+    }
+
+    #[test]
+    fn capacities_change_important_cuts() {
+        let graph = Graph::<(), u32, Undirected>::from_edges([
+            (0, 1, 5),
+            (1, 2, 1),
+            (1, 3, 1),
+            (2, 4, 1),
+            (3, 4, 1),
+        ]);
+
+        let result = important_cuts(&graph, vec![0], vec![4], 2);
+        assert_eq!(
+            vec![vec![0], vec![3, 4]],
+            ImportantCut::vec_edge_indices(result)
+        );
+
+        let result =
+            important_cuts_with_capacities(&graph, vec![0], vec![4], 2, |&weight| weight as usize);
+        assert_eq!(vec![vec![3, 4]], ImportantCut::vec_edge_indices(result));
+    }
+
+    #[test]
+    fn huge_capacities_are_not_expanded() {
+        let graph = Graph::<(), u32, Undirected>::from_edges([
+            (0, 1, 4_000_000_000),
+            (1, 2, 1),
+            (1, 3, 1),
+            (2, 4, 1),
+            (3, 4, 4_000_000_000),
+        ]);
+
+        let result =
+            important_cuts_with_capacities(&graph, vec![0], vec![4], 2, |&weight| weight as usize);
+        assert_eq!(vec![vec![2, 3]], ImportantCut::vec_edge_indices(result));
+    }
+
+    #[test]
+    fn filtered_node_removes_cut_through_it() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2), (1, 3), (2, 4), (3, 4)]);
+
+        let result = important_cuts(&graph, vec![0], vec![4], 2);
+        assert_eq!(
+            vec![vec![0], vec![3, 4]],
+            ImportantCut::vec_edge_indices(result)
+        );
+
+        // without vertex 3 the graph is a path, whose edge closest to the destination is enough
+        let result = important_cuts_with_node_filter(&graph, vec![0], vec![4], 2, |v| v != 3);
+        assert_eq!(vec![vec![3]], ImportantCut::vec_edge_indices(result));
+    }
+
+    #[test]
+    fn depth_zero_finds_initial_min_cut() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![ImportantCut::from(vec![0, 1])],
+            important_cuts_with_max_depth(&graph, vec![0], destination, 3, Some(0))
+        );
+    }
+
+    #[test]
+    fn sorted_binary_tree_cuts() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect();
+
+        let result = important_cuts(&graph, vec![0], destination, 3);
+        assert!(result
+            .windows(2)
+            .all(|pair| pair[0].edge_indices().len() <= pair[1].edge_indices().len()));
+        assert_eq!(
+            vec![vec![0, 1], vec![0, 4, 5], vec![1, 2, 3]],
+            ImportantCut::vec_edge_indices(result)
+        );
+    }
+
+    #[test]
+    fn iterator_stops_early() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+        let all_cuts = important_cuts(&graph, vec![0], destination.clone(), 3);
+
+        let mut iter = important_cuts_iter(&graph, vec![0], destination.clone(), 3);
+        let first_two = iter.by_ref().take(2).collect::<Vec<_>>();
+        assert_eq!(2, first_two.len());
+        assert!(first_two.iter().all(|cut| all_cuts.contains(cut)));
+        // some branches have not been explored yet
+        assert!(!iter.stack.is_empty());
+
+        let mut iterated = important_cuts_iter(&graph, vec![0], destination, 3).collect::<Vec<_>>();
+        iterated.sort();
+        assert_eq!(all_cuts, iterated);
+    }
+
+    #[test]
+    fn cuts_through_channel() {
+        let graph = create_binary_tree(3);
+        let destination = (3..=6).collect::<Vec<_>>();
+        let expected = important_cuts(&graph, vec![0], destination.clone(), 3);
+
+        let (tx, rx) = mpsc::channel();
+        let consumer = thread::spawn(move || rx.iter().collect::<Vec<_>>());
+        important_cuts_into_channel(&graph, vec![0], destination, 3, tx);
+
+        let mut received = consumer.join().unwrap();
+        received.sort();
+        assert_eq!(expected, received);
+    }
+
+    #[test]
+    fn u32_indices_match_usize_indices() {
+        let edges: [(u32, u32); 7] = [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (2, 6), (4, 6)];
+        let graph = UnGraph::from_edges(edges.map(|(a, b)| (a as usize, b as usize)));
+        let small_graph = to_undirected(&to_directed(
+            &Graph::<(), (), Undirected, u32>::from_edges(edges),
+        ));
+
+        assert_eq!(
+            important_cuts(&graph, vec![0], vec![3, 5, 6], 3),
+            important_cuts(&small_graph, vec![0], vec![3, 5, 6], 3)
+        );
+        let sorted_cut = |mut cut: Cut| {
+            cut.source_set.sort();
+            cut.destination_set.sort();
+            cut.cut_edge_set.sort();
+            cut
+        };
+        let min_cut = try_minimum_cut(&graph, vec![0], vec![3, 5, 6])
+            .unwrap()
+            .unwrap();
+        let small_min_cut = try_minimum_cut(&small_graph, vec![0], vec![3, 5, 6])
+            .unwrap()
+            .unwrap();
+        assert_eq!(sorted_cut(min_cut), sorted_cut(small_min_cut));
+    }
+
+    #[test]
+    fn stable_graph_with_removed_node() {
+        // 0 - 2 - 3 - 4 after removing vertex 1 together with edges 0 and 1
+        let mut graph =
+            StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (0, 2)]);
+        graph.remove_node(NodeIndex::new(1));
+
+        let result = important_cuts(&graph, vec![0], vec![4], 1);
+        assert_eq!(1, result.len());
+        assert_eq!(vec![3], result[0].edge_indices());
+        assert_eq!(vec![(3, 4)], result[0].vertex_pairs(&graph));
+
+        let result = important_cuts(&graph, vec![0], vec![3], 1);
+        assert_eq!(vec![vec![2]], ImportantCut::vec_edge_indices(result));
+    }
+
+    #[test]
+    fn payloads_of_cut_edges() {
+        let mut graph = petgraph::graph::UnGraph::<&str, String>::new_undirected();
+        let vertices = ["a", "b", "c", "d"].map(|name| graph.add_node(name));
+        graph.add_edge(vertices[0], vertices[1], "a-b".to_string());
+        graph.add_edge(vertices[1], vertices[2], "b-c".to_string());
+        graph.add_edge(vertices[1], vertices[3], "b-d".to_string());
+
+        let result = important_cuts_with_payloads(&graph, vec![0], vec![2, 3], 2);
+        assert!(!result.is_empty());
+        for (cut, payloads) in &result {
+            assert_eq!(cut.edge_indices().len(), payloads.len());
+            for (&edge_index, payload) in cut.edge_indices().iter().zip(payloads) {
+                assert_eq!(["a-b", "b-c", "b-d"][edge_index], payload.as_str());
+            }
+        }
+        assert_eq!(
+            vec![vec!["a-b"], vec!["b-c", "b-d"]],
+            result
+                .iter()
+                .map(|(_, payloads)| payloads.iter().map(|payload| payload.as_str()).collect())
+                .collect::<Vec<Vec<_>>>()
+        );
+    }
+
+    #[test]
+    fn try_important_cuts_rejects_invalid_sets() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2)]);
+
+        assert_eq!(
+            Err(Error::EmptySet),
+            try_important_cuts(&graph, vec![], vec![2], 1)
+        );
+        assert_eq!(
+            Err(Error::OverlappingSets(1)),
+            try_important_cuts(&graph, vec![0, 1], vec![1, 2], 1)
+        );
+        // vertices that are not in the graph are not connected to anything
+        assert_eq!(
+            Ok(vec![ImportantCut::from(vec![])]),
+            try_important_cuts(&graph, vec![0], vec![7], 1)
+        );
+    }
+
+    #[test]
+    fn try_variants_reject_invalid_input() {
+        let graph = UnGraph::from_edges([(0, 1), (1, 2)]);
+
+        assert_eq!(
+            Err(Error::MissingEdge(7)),
+            try_important_cuts_with_excluded_edges(&graph, vec![0], vec![2], 1, &[0, 7])
+        );
+        assert_eq!(
+            Err(Error::MissingEdge(7)),
+            try_important_cuts_with_forced_edges(&graph, vec![0], vec![2], 1, &[7])
+        );
+        assert_eq!(
+            Err(Error::FilteredVertex(2)),
+            try_important_cuts_with_node_filter(&graph, vec![0], vec![2], 1, |vertex| vertex != 2)
+        );
+        assert_eq!(
+            Err(Error::OverlappingSets(1)),
+            try_important_cuts_with_capacities(&graph, vec![0, 1], vec![1], 1, |_| 1)
+        );
+        assert_eq!(
+            Err(Error::EmptySet),
+            try_smallest_important_cut(&graph, vec![0], vec![], 1)
+        );
+        assert!(try_important_cuts_iter(&graph, vec![], vec![2], 1).is_err());
+    }
+
+    #[test]
+    fn unreachable_destination_has_only_the_empty_cut() {
+        // the source set is a whole component and the destination is isolated from it
+        let mut graph = UnGraph::from_edges([(0, 1), (1, 2), (2, 0), (1, 3)]);
+        graph.add_node(());
+
+        let (cuts, stats) = important_cuts_with_stats(&graph, vec![0, 1, 2, 3], vec![4], 3);
+        assert_eq!(vec![ImportantCut::from(vec![])], cuts);
+        assert_eq!(1, stats.branches_explored);
+    }
+
+    /// Graph with at most `max_vertices` vertices, together with disjoint nonempty source and
+    /// destination sets.
+    fn instance(max_vertices: usize) -> impl Strategy<Value = (UnGraph, Vec<usize>, Vec<usize>)> {
+        (2..=max_vertices)
+            .prop_flat_map(|vertex_count| {
+                (
+                    Just(vertex_count),
+                    vec(
+                        (0..vertex_count, 0..vertex_count),
+                        vertex_count..=2 * vertex_count,
+                    ),
+                    Just((0..vertex_count).collect::<Vec<_>>()).prop_shuffle(),
+                    1..=vertex_count / 2,
+                    1..=vertex_count / 2,
+                )
+            })
+            .prop_map(
+                |(vertex_count, edges, vertices, source_count, destination_count)| {
+                    let mut graph = UnGraph::from_edges(edges);
+                    while graph.node_count() < vertex_count {
+                        graph.add_node(());
+                    }
+                    let source_set = vertices[..source_count].to_vec();
+                    let destination_set =
+                        vertices[source_count..source_count + destination_count].to_vec();
+                    (graph, source_set, destination_set)
+                },
+            )
+    }
+
+    /// Graph with gaps in its vertex indices, together with sets that may be empty, overlap or
+    /// contain vertices that are not in the graph.
+    fn arbitrary_instance(
+    ) -> impl Strategy<Value = (StableUnGraph<(), (), usize>, Vec<usize>, Vec<usize>)> {
+        (1..8usize)
+            .prop_flat_map(|vertex_count| {
+                (
+                    vec((0..vertex_count, 0..vertex_count), 0..12),
+                    option::weighted(0.3, any::<Index>()),
+                    vec(0..vertex_count + 2, 0..4),
+                    vec(0..vertex_count + 2, 0..4),
+                )
+            })
+            .prop_map(|(edges, removed, source_set, destination_set)| {
+                let mut graph = StableUnGraph::<(), (), usize>::from_edges(edges);
+                if let Some(removed) = removed.filter(|_| graph.node_count() > 1) {
+                    graph.remove_node(NodeIndex::new(removed.index(graph.node_count())));
+                }
+                (graph, source_set, destination_set)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn important_cuts_match_brute_force(
+            (graph, source_set, destination_set) in instance(8),
+            k in 1..=4usize,
+        ) {
+            let cuts = important_cuts(&graph, source_set.clone(), destination_set.clone(), k);
+            let flow =
+                try_max_flow_between_sets(&graph, source_set.clone(), destination_set.clone());
+            if flow == Ok(0) {
+                // the brute force cannot contract sets that are not connected to anything
+                prop_assert_eq!(vec![ImportantCut::from(vec![])], cuts);
+            } else {
+                let brute_force = filter_important_cuts(&generate_cuts_for_sets(
+                    &graph,
+                    source_set,
+                    destination_set,
+                    k,
+                ))
+                .into_iter()
+                .map(|cut| cut.cut_edge_set)
+                .collect::<BTreeSet<_>>();
+                let cuts = cuts
+                    .into_iter()
+                    .map(ImportantCut::into_edge_indices)
+                    .collect::<BTreeSet<_>>();
+                prop_assert_eq!(brute_force, cuts);
+            }
+        }
+
+        #[test]
+        fn branches_never_repeat_a_subproblem(
+            (graph, source_set, destination_set) in instance(8),
+        ) {
+            let mut iter = important_cuts_iter(&graph, source_set, destination_set, 4);
+            let mut subproblems = BTreeSet::new();
+            while let Some(branch) = iter.stack.last() {
+                let mut source_set = branch.source_set.clone();
+                source_set.sort();
+                prop_assert!(subproblems.insert((source_set, branch.edges_in_use.clone())));
+                iter.explore_next_branch();
+            }
+        }
+
+        #[test]
+        fn try_variants_never_panic(
+            (graph, source_set, destination_set) in arbitrary_instance(),
+            k in 0..4usize,
+        ) {
+            let cuts = try_important_cuts(&graph, source_set.clone(), destination_set.clone(), k);
+            let min_cut = try_minimum_cut(&graph, source_set.clone(), destination_set.clone());
+            let max_flow = try_max_flow_between_sets(&graph, source_set, destination_set);
+            prop_assert_eq!(cuts.is_ok(), min_cut.is_ok());
+            prop_assert_eq!(cuts.is_ok(), max_flow.is_ok());
+        }
     }
 }
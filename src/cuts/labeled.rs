@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+
+use crate::cuts::path_residual::UnGraph;
+
+/// Builds an undirected graph whose vertices are named, e.g. after the entities of a network, so
+/// that the indices in the results of the algorithms can be translated back to the names.
+#[derive(Debug, Default)]
+pub struct LabeledGraphBuilder {
+    graph: UnGraph,
+    indices: HashMap<String, usize>,
+}
+
+impl LabeledGraphBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a vertex with the label and returns its index. If there already is a vertex with the
+    /// label, its index is returned instead.
+    pub fn add_node(&mut self, label: &str) -> usize {
+        if let Some(&index) = self.indices.get(label) {
+            return index;
+        }
+        let index = self.graph.add_node(()).index();
+        self.indices.insert(label.to_string(), index);
+        index
+    }
+
+    /// Adds an edge between the vertices with the labels and returns its index. Vertices that do
+    /// not exist yet are added first.
+    pub fn add_edge(&mut self, source: &str, destination: &str) -> usize {
+        let source = self.add_node(source);
+        let destination = self.add_node(destination);
+        self.graph
+            .add_edge(NodeIndex::new(source), NodeIndex::new(destination), ())
+            .index()
+    }
+
+    pub fn build(self) -> LabeledGraph {
+        let labels = self
+            .indices
+            .iter()
+            .map(|(label, &index)| (index, label.clone()))
+            .collect();
+        LabeledGraph {
+            graph: self.graph,
+            indices: self.indices,
+            labels,
+        }
+    }
+}
+
+/// A graph built by [`LabeledGraphBuilder`], with the index of each label and its inverse.
+#[derive(Debug)]
+pub struct LabeledGraph {
+    pub graph: UnGraph,
+    pub indices: HashMap<String, usize>,
+    pub labels: HashMap<usize, String>,
+}
+
+impl LabeledGraph {
+    /// Labels of the endpoints of the edge, or `None` if the edge does not exist.
+    pub fn edge_labels(&self, edge: usize) -> Option<(&str, &str)> {
+        let (a, b) = self.graph.edge_endpoints(EdgeIndex::new(edge))?;
+        Some((
+            self.labels[&a.index()].as_str(),
+            self.labels[&b.index()].as_str(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cuts::labeled::LabeledGraphBuilder;
+    use crate::cuts::minimum_cut::minimum_cut;
+
+    #[test]
+    fn cut_edge_resolves_to_names() {
+        let mut builder = LabeledGraphBuilder::new();
+        let client = builder.add_node("client");
+        assert_eq!(client, builder.add_node("client"));
+        builder.add_edge("client", "load-balancer");
+        builder.add_edge("load-balancer", "web-server");
+        builder.add_edge("load-balancer", "web-server");
+        builder.add_edge("web-server", "db");
+        let labeled = builder.build();
+
+        assert_eq!(4, labeled.graph.node_count());
+        assert_eq!("db", labeled.labels[&labeled.indices["db"]]);
+
+        let cut = minimum_cut(&labeled.graph, vec![client], vec![labeled.indices["db"]]).unwrap();
+        assert_eq!(vec![3], cut.cut_edge_set);
+        assert_eq!(Some(("web-server", "db")), labeled.edge_labels(3));
+        assert_eq!(None, labeled.edge_labels(4));
+    }
+}
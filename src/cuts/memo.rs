@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+use fixedbitset::FixedBitSet;
+
+use crate::cuts::cut::Cut;
+use crate::cuts::path_residual::Path;
+
+/// How the flow computation of a branch of the search for important cuts ended.
+#[derive(Debug, Clone)]
+pub(crate) enum BranchOutcome {
+    /// The destination set cannot be reached from the source set
+    Disconnected,
+    /// The minimum cut is larger than the given bound
+    LargerThan(usize),
+    /// The minimum cut closest to the destination set, with the paths of the flow it was read from
+    MinimumCut(Cut, Vec<Path>),
+}
+
+/// How often a [`CutSolver`](crate::cuts::CutSolver) found the minimum cut of a branch in its
+/// memo, see [`CutSolver::memo_stats`](crate::cuts::CutSolver::memo_stats).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    /// Number of branches whose minimum cut was taken from the memo
+    pub hits: usize,
+    /// Number of branches whose minimum cut had to be computed
+    pub misses: usize,
+    /// Number of branches stored in the memo
+    pub entries: usize,
+}
+
+/// Outcomes of the branches explored so far, keyed on the sorted source set and the edges in use
+/// of each branch, which together with the graph and the destination set determine its minimum
+/// cut. Within one search every branch is a different subproblem, so the memo pays off when
+/// searches are repeated on the same graph, e.g. for another k or after an edge has been removed.
+///
+/// Once `capacity` branches are stored, further branches are computed but not stored.
+#[derive(Debug, Default)]
+pub(crate) struct BranchMemo {
+    outcomes: HashMap<(Vec<usize>, FixedBitSet), BranchOutcome>,
+    capacity: Option<usize>,
+    hits: usize,
+    misses: usize,
+}
+
+impl BranchMemo {
+    pub(crate) fn new(capacity: Option<usize>) -> Self {
+        Self {
+            capacity,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the outcome of the branch for the bound k, or `None` if it is not stored or was
+    /// stored for a smaller bound that the minimum cut exceeded.
+    pub(crate) fn get(
+        &mut self,
+        source_set: &[usize],
+        edges_in_use: &[bool],
+        k: usize,
+    ) -> Option<BranchOutcome> {
+        let outcome = match self.outcomes.get(&key(source_set, edges_in_use)) {
+            Some(BranchOutcome::MinimumCut(cut, _)) if cut.size > k => {
+                Some(BranchOutcome::LargerThan(k))
+            }
+            Some(BranchOutcome::LargerThan(bound)) if *bound < k => None,
+            outcome => outcome.cloned(),
+        };
+        if outcome.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        outcome
+    }
+
+    /// Stores the outcome of the branch, unless the memo is full.
+    pub(crate) fn insert(
+        &mut self,
+        source_set: &[usize],
+        edges_in_use: &[bool],
+        outcome: BranchOutcome,
+    ) {
+        let key = key(source_set, edges_in_use);
+        if self
+            .capacity
+            .is_some_and(|capacity| self.outcomes.len() >= capacity)
+            && !self.outcomes.contains_key(&key)
+        {
+            return;
+        }
+        self.outcomes.insert(key, outcome);
+    }
+
+    /// Forgets the stored outcomes, e.g. after the graph has changed, but keeps the counters.
+    pub(crate) fn clear(&mut self) {
+        self.outcomes.clear();
+    }
+
+    pub(crate) fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.outcomes.len(),
+        }
+    }
+}
+
+fn key(source_set: &[usize], edges_in_use: &[bool]) -> (Vec<usize>, FixedBitSet) {
+    let mut source_set = source_set.to_vec();
+    source_set.sort_unstable();
+    let mut bitset = FixedBitSet::with_capacity(edges_in_use.len());
+    for (edge, &in_use) in edges_in_use.iter().enumerate() {
+        bitset.set(edge, in_use);
+    }
+    (source_set, bitset)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cuts::cut::Cut;
+    use crate::cuts::memo::{BranchMemo, BranchOutcome, MemoStats};
+
+    #[test]
+    fn outcomes_depend_on_the_bound() {
+        let mut memo = BranchMemo::new(None);
+        let cut = Cut::new(vec![0, 1], vec![2], vec![1]);
+        memo.insert(
+            &[1, 0],
+            &[true, true],
+            BranchOutcome::MinimumCut(cut, vec![]),
+        );
+        memo.insert(&[0], &[true, false], BranchOutcome::LargerThan(2));
+
+        // the source set is sorted, but the edges in use have to match
+        assert!(matches!(
+            memo.get(&[0, 1], &[true, true], 1),
+            Some(BranchOutcome::MinimumCut(..))
+        ));
+        assert!(matches!(
+            memo.get(&[0, 1], &[true, true], 0),
+            Some(BranchOutcome::LargerThan(0))
+        ));
+        assert!(memo.get(&[0, 1], &[true, false], 1).is_none());
+        assert!(matches!(
+            memo.get(&[0], &[true, false], 2),
+            Some(BranchOutcome::LargerThan(2))
+        ));
+        assert!(memo.get(&[0], &[true, false], 3).is_none());
+        assert_eq!(
+            MemoStats {
+                hits: 3,
+                misses: 2,
+                entries: 2,
+            },
+            memo.stats()
+        );
+    }
+
+    #[test]
+    fn full_memo_keeps_its_entries() {
+        let mut memo = BranchMemo::new(Some(1));
+        memo.insert(&[0], &[true], BranchOutcome::LargerThan(1));
+        memo.insert(&[1], &[true], BranchOutcome::Disconnected);
+        memo.insert(&[0], &[true], BranchOutcome::LargerThan(3));
+
+        assert!(memo.get(&[1], &[true], 1).is_none());
+        assert!(matches!(
+            memo.get(&[0], &[true], 3),
+            Some(BranchOutcome::LargerThan(3))
+        ));
+        assert_eq!(1, memo.stats().entries);
+    }
+}
@@ -0,0 +1,1099 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+
+use crate::cuts::compaction::Compaction;
+use crate::cuts::cut::{
+    generate_minimum_cut_closest_to_destination,
+    generate_minimum_cut_closest_to_destination_with_mapping,
+    generate_minimum_cut_closest_to_source, map_cut_to_original, Cut,
+};
+use crate::cuts::flow::{
+    max_flow_value_between_sets, max_flow_with_capacities_between_sets,
+    vertices_reaching_destination,
+};
+use crate::cuts::path_residual::{
+    augmenting_paths, get_augmenting_paths_and_residual_graph_for_sets, map_paths_to_original,
+    Path, UnGraph,
+};
+use crate::cuts::verify::{reachable_vertices, validate_sets};
+use crate::Error;
+
+/// Finds the minimum cut between the source and destination sets that is closest to the
+/// destination set. Returns `None` if the sets are not connected to begin with.
+///
+/// The vertex and edge indices of the cut are the ones of the given graph, which do not have to
+/// be contiguous. Vertices that are not incident to any edge are left out of both sides of the cut,
+/// unless they are in one of the sets.
+/// Panics if one of the sets is empty or the sets overlap, see [`try_minimum_cut`].
+pub fn minimum_cut<G>(graph: G, source_set: Vec<usize>, destination_set: Vec<usize>) -> Option<Cut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_minimum_cut(graph, source_set, destination_set).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`minimum_cut`], but returns an error instead of panicking if one of the sets is empty
+/// or the sets overlap.
+pub fn try_minimum_cut<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Result<Option<Cut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    let edge_count = compacted_graph.edge_count();
+
+    get_augmenting_paths_and_residual_graph_for_sets(
+        &compacted_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+        edge_count,
+        &vec![true; edge_count],
+    )
+    .map(|(paths, residual, index_mapping)| {
+        generate_minimum_cut_closest_to_destination_with_mapping(&paths, residual, index_mapping)
+            .map(|min_cut| compaction.original_cut(min_cut))
+    })
+    .transpose()
+}
+
+/// A minimum cut together with the augmenting paths of the maximum flow it was read from, all in
+/// the indices of the input graph.
+#[derive(Debug)]
+pub struct ExplainedCut {
+    pub cut: Cut,
+    pub paths: Vec<Path>,
+    /// Maps each cut edge to the indices of the paths that lie on it. Every path crosses a minimum
+    /// cut exactly once, so each cut edge blocks exactly one path.
+    pub blocked_paths: HashMap<usize, Vec<usize>>,
+}
+
+/// Same as [`minimum_cut`], but also returns the paths of the maximum flow and which of them each
+/// cut edge blocks, to explain why the cut is minimum. Panics if one of the sets is empty or the
+/// sets overlap, see [`try_explained_minimum_cut`].
+pub fn explained_minimum_cut<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Option<ExplainedCut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_explained_minimum_cut(graph, source_set, destination_set)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`explained_minimum_cut`], but returns an error instead of panicking if one of the sets
+/// is empty or the sets overlap.
+pub fn try_explained_minimum_cut<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Result<Option<ExplainedCut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    let edges_in_use = vec![true; compacted_graph.edge_count()];
+
+    let Some((paths, residual, index_mapping)) = get_augmenting_paths_and_residual_graph_for_sets(
+        &compacted_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+        edges_in_use.len(),
+        &edges_in_use,
+    ) else {
+        return Ok(None);
+    };
+    let witness_paths =
+        map_paths_to_original(&compacted_graph, &paths, &index_mapping, &edges_in_use)
+            .into_iter()
+            .map(|path| Path {
+                vertices: compaction.original_vertices(&path.vertices),
+                edges: compaction.original_edges(&path.edges),
+            })
+            .collect::<Vec<_>>();
+    let cut = compaction.original_cut(
+        generate_minimum_cut_closest_to_destination_with_mapping(&paths, residual, index_mapping)
+            .expect("The index mapping should belong to the contracted graph"),
+    );
+
+    let blocked_paths = cut
+        .cut_edge_set
+        .iter()
+        .map(|&edge| {
+            let path_indices = witness_paths
+                .iter()
+                .positions(|path| path.edges.contains(&edge))
+                .collect();
+            (edge, path_indices)
+        })
+        .collect();
+
+    Ok(Some(ExplainedCut {
+        cut,
+        paths: witness_paths,
+        blocked_paths,
+    }))
+}
+
+/// Same as [`minimum_cut`], but finds the minimum cut that is closest to the source set, i.e. the
+/// one with the smallest source side. Both cuts are read from the residual graph of the same flow.
+/// Panics if one of the sets is empty or the sets overlap, see
+/// [`try_minimum_cut_closest_to_source`].
+pub fn minimum_cut_closest_to_source<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Option<Cut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_minimum_cut_closest_to_source(graph, source_set, destination_set)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`minimum_cut_closest_to_source`], but returns an error instead of panicking if one of
+/// the sets is empty or the sets overlap.
+pub fn try_minimum_cut_closest_to_source<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Result<Option<Cut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    let edge_count = compacted_graph.edge_count();
+
+    Ok(get_augmenting_paths_and_residual_graph_for_sets(
+        &compacted_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+        edge_count,
+        &vec![true; edge_count],
+    )
+    .map(|(paths, residual, index_mapping)| {
+        let min_cut = generate_minimum_cut_closest_to_source(&paths, &residual);
+        compaction.original_cut(
+            map_cut_to_original(min_cut, &index_mapping)
+                .expect("The index mapping should belong to the contracted graph"),
+        )
+    }))
+}
+
+/// Finds both extreme minimum cuts between the source and destination sets from a single flow
+/// computation, returned as `(closest_to_source, closest_to_destination)`. Every other minimum cut
+/// lies between these two, and if the minimum cut is unique, the two are equal. Returns `None` if
+/// the sets are not connected or the size of the minimum cut is larger than k. Panics if one of the
+/// sets is empty or the sets overlap, see [`try_extreme_minimum_cuts`].
+pub fn extreme_minimum_cuts<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Option<(Cut, Cut)>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_extreme_minimum_cuts(graph, source_set, destination_set, k)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`extreme_minimum_cuts`], but returns an error instead of panicking if one of the sets
+/// is empty or the sets overlap.
+pub fn try_extreme_minimum_cuts<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Result<Option<(Cut, Cut)>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+
+    Ok(get_augmenting_paths_and_residual_graph_for_sets(
+        &compacted_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+        k,
+        &vec![true; compacted_graph.edge_count()],
+    )
+    .map(|(paths, residual, index_mapping)| {
+        let closest_to_source = generate_minimum_cut_closest_to_source(&paths, &residual);
+        let closest_to_destination = generate_minimum_cut_closest_to_destination(&paths, &residual);
+        (
+            compaction.original_cut(
+                map_cut_to_original(closest_to_source, &index_mapping)
+                    .expect("The index mapping should belong to the contracted graph"),
+            ),
+            compaction.original_cut(
+                map_cut_to_original(closest_to_destination, &index_mapping)
+                    .expect("The index mapping should belong to the contracted graph"),
+            ),
+        )
+    }))
+}
+
+/// Finds every minimum cut between the source and destination sets, sorted by their sorted edges.
+/// Vertices in components of the graph that contain neither set are on the source side. If the
+/// sets are not connected, the only minimum cut is the empty cut, whose source side is the
+/// component of the source set. Panics if one of the sets is empty or the sets overlap, see
+/// [`try_minimum_cardinality_cuts`].
+///
+/// The source sides of the minimum cuts are exactly the sets of vertices that contain the source
+/// set, avoid the destination set and are closed under the arcs of the residual graph of a maximum
+/// flow. Instead of going through the antichains of the condensed residual graph, the closure of
+/// every vertex is precomputed, which takes O(V²) time and memory, and the closed sets are
+/// enumerated by branching on whether a vertex is in the source side. Every branch ends in a
+/// different minimum cut, so the running time grows with the number of minimum cuts, which can be
+/// exponential in the size of the graph.
+pub fn minimum_cardinality_cuts<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Vec<Cut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_minimum_cardinality_cuts(graph, source_set, destination_set)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`minimum_cardinality_cuts`], but returns an error instead of panicking if one of the
+/// sets is empty or the sets overlap.
+pub fn try_minimum_cardinality_cuts<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Result<Vec<Cut>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    let edge_count = compacted_graph.edge_count();
+    let edges_in_use = vec![true; edge_count];
+    let compact_source_set = compaction.compact_vertices(&source_set);
+    let compact_destination_set = compaction.compact_vertices(&destination_set);
+
+    // without any flow, every edge can be traversed in both directions, so the closed sets below
+    // only leave the empty cut
+    let paths = match get_augmenting_paths_and_residual_graph_for_sets(
+        &compacted_graph,
+        compact_source_set.clone(),
+        compact_destination_set.clone(),
+        edge_count,
+        &edges_in_use,
+    ) {
+        Some((paths, _, index_mapping)) => {
+            map_paths_to_original(&compacted_graph, &paths, &index_mapping, &edges_in_use)
+        }
+        None => vec![],
+    };
+
+    // an edge that carries flow can only be traversed against the flow in the residual graph,
+    // while every other edge can be traversed in both directions
+    let vertex_count = compacted_graph.node_count();
+    let mut flow_from = vec![None; edge_count];
+    for path in &paths {
+        for (i, &edge) in path.edges.iter().enumerate() {
+            flow_from[edge] = Some(path.vertices[i]);
+        }
+    }
+    let mut residual_arcs = vec![vec![]; vertex_count];
+    for edge in compacted_graph.edge_references() {
+        let (source, target) = (edge.source().index(), edge.target().index());
+        match flow_from[edge.id().index()] {
+            Some(from) if from == source => residual_arcs[target].push(source),
+            Some(_) => residual_arcs[source].push(target),
+            None => {
+                residual_arcs[source].push(target);
+                residual_arcs[target].push(source);
+            }
+        }
+    }
+    let closures = (0..vertex_count)
+        .map(|vertex| residual_closure(&residual_arcs, &[vertex]))
+        .collect::<Vec<_>>();
+
+    // the source side has to contain everything that can be reached from the source set, and
+    // cannot contain any vertex from which the destination set can be reached
+    let mut in_source_side = residual_closure(&residual_arcs, &compact_source_set);
+    // the side of a vertex in a component without either set does not change the cut edges, so
+    // it is fixed instead of branching on it
+    let connected_to_sets = reachable_vertices(
+        &compacted_graph,
+        &[compact_source_set.clone(), compact_destination_set.clone()].concat(),
+        |_| true,
+    );
+    for (vertex, in_source_side) in in_source_side.iter_mut().enumerate() {
+        if !connected_to_sets.contains(&vertex) {
+            *in_source_side = true;
+        }
+    }
+    let mut excluded = vec![false; vertex_count];
+    for vertex in 0..vertex_count {
+        if compact_destination_set
+            .iter()
+            .any(|&destination| closures[vertex][destination])
+        {
+            excluded[vertex] = true;
+        }
+    }
+
+    let mut source_sides = vec![];
+    enumerate_closed_sets(
+        &closures,
+        0,
+        &mut in_source_side,
+        &mut excluded,
+        &mut source_sides,
+    );
+
+    let mut cuts = source_sides
+        .into_iter()
+        .map(|in_source_side| {
+            let (source_side, destination_side): (Vec<usize>, Vec<usize>) =
+                (0..vertex_count).partition(|&vertex| in_source_side[vertex]);
+            let cut_edges = compacted_graph
+                .edge_references()
+                .filter(|edge| {
+                    in_source_side[edge.source().index()] != in_source_side[edge.target().index()]
+                })
+                .map(|edge| edge.id().index())
+                .collect();
+            let mut cut =
+                compaction.original_cut(Cut::new(source_side, destination_side, cut_edges));
+            cut.source_set.sort();
+            cut.destination_set.sort();
+            cut.cut_edge_set.sort();
+            cut
+        })
+        .collect::<Vec<_>>();
+    cuts.sort_by(|a, b| a.cut_edge_set.cmp(&b.cut_edge_set));
+    Ok(cuts)
+}
+
+/// Vertices that can be reached from the start vertices along the given arcs, as a mask.
+fn residual_closure(arcs: &[Vec<usize>], start: &[usize]) -> Vec<bool> {
+    let mut reached = vec![false; arcs.len()];
+    let mut stack = start.to_vec();
+    while let Some(vertex) = stack.pop() {
+        if !reached[vertex] {
+            reached[vertex] = true;
+            stack.extend(&arcs[vertex]);
+        }
+    }
+    reached
+}
+
+/// Collects the closed sets that extend `in_source_side` by deciding for each vertex from `vertex`
+/// onwards whether its closure is added to the set or whether it is excluded from it.
+fn enumerate_closed_sets(
+    closures: &[Vec<bool>],
+    vertex: usize,
+    in_source_side: &mut Vec<bool>,
+    excluded: &mut Vec<bool>,
+    closed_sets: &mut Vec<Vec<bool>>,
+) {
+    let Some(vertex) = (vertex..closures.len()).find(|&v| !in_source_side[v] && !excluded[v])
+    else {
+        closed_sets.push(in_source_side.clone());
+        return;
+    };
+
+    // the vertex is on the source side, and so is everything that can be reached from it
+    let saved_source_side = in_source_side.clone();
+    for (other, &reached) in closures[vertex].iter().enumerate() {
+        if reached {
+            in_source_side[other] = true;
+        }
+    }
+    enumerate_closed_sets(closures, vertex + 1, in_source_side, excluded, closed_sets);
+    *in_source_side = saved_source_side;
+
+    // the vertex is on the destination side, and so is everything from which it can be reached
+    let saved_excluded = excluded.clone();
+    for (other, closure) in closures.iter().enumerate() {
+        if closure[vertex] {
+            excluded[other] = true;
+        }
+    }
+    enumerate_closed_sets(closures, vertex + 1, in_source_side, excluded, closed_sets);
+    *excluded = saved_excluded;
+}
+
+/// Finds the minimum cut between the source and destination sets whose edges have the smallest
+/// total weight, where `weight_of` gives the weight of an edge of the graph by its index. Returns
+/// the cut together with its weight, or `None` if the sets are not connected by edges of positive
+/// weight. Panics if one of the sets is empty, the sets overlap or the total weight of the edges
+/// overflows, see [`try_minimum_weight_cut`].
+///
+/// The flow is computed with the weights as capacities, so the running time does not depend on the
+/// weights, but their total has to fit into an `isize`. Edges of weight zero that cross the cut
+/// are part of it.
+pub fn minimum_weight_cut<G, F>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    weight_of: F,
+) -> Option<(Cut, u64)>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(usize) -> u64,
+{
+    try_minimum_weight_cut(graph, source_set, destination_set, weight_of)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`minimum_weight_cut`], but returns an error instead of panicking if one of the sets is
+/// empty, the sets overlap or the total weight of the edges does not fit into an `isize`.
+pub fn try_minimum_weight_cut<G, F>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    weight_of: F,
+) -> Result<Option<(Cut, u64)>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(usize) -> u64,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+
+    // the flow can be at most the total weight, which therefore has to fit into the flows
+    let weights = compaction
+        .original_edges(&(0..compacted_graph.edge_count()).collect::<Vec<_>>())
+        .into_iter()
+        .map(&weight_of)
+        .collect::<Vec<_>>();
+    weights
+        .iter()
+        .try_fold(0isize, |total, &weight| {
+            isize::try_from(weight)
+                .ok()
+                .and_then(|weight| total.checked_add(weight))
+        })
+        .ok_or(Error::Overflow)?;
+    let capacities = weights
+        .iter()
+        .map(|&weight| weight as usize)
+        .collect::<Vec<_>>();
+
+    let compact_destination_set = compaction.compact_vertices(&destination_set);
+    let (flow_value, edge_flows) = max_flow_with_capacities_between_sets(
+        &compacted_graph,
+        &compaction.compact_vertices(&source_set),
+        &compact_destination_set,
+        &capacities,
+        usize::MAX,
+    );
+    if flow_value == 0 {
+        return Ok(None);
+    }
+
+    // the cut closest to the destination set has the vertices that can still reach the
+    // destination set through the residual graph on its destination side
+    let in_destination_side = vertices_reaching_destination(
+        &compacted_graph,
+        &compact_destination_set,
+        &capacities,
+        &edge_flows,
+    );
+    let (destination_side, source_side) =
+        (0..compacted_graph.node_count()).partition(|&vertex| in_destination_side[vertex]);
+    let cut_edges = compacted_graph
+        .edge_references()
+        .filter(|edge| {
+            in_destination_side[edge.source().index()] != in_destination_side[edge.target().index()]
+        })
+        .map(|edge| edge.id().index())
+        .collect();
+
+    let cut = compaction.original_cut(Cut::new(source_side, destination_side, cut_edges));
+    let weight = cut.try_weight(weight_of)?;
+    Ok(Some((cut, weight)))
+}
+
+/// Same as [`minimum_weight_cut`], but the weight of each edge is its capacity, which
+/// `capacity_fn` reads from the payload of the edge, e.g. `|&weight| weight as usize` for a
+/// `Graph<(), u32>`. Panics if one of the sets is empty, the sets overlap or the total capacity of
+/// the edges does not fit into an `isize`.
+pub fn minimum_cut_with_capacities<G, F>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    capacity_fn: F,
+) -> Option<(Cut, u64)>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+    F: Fn(&G::EdgeWeight) -> usize,
+{
+    let capacities = edge_capacities(graph, capacity_fn);
+    minimum_weight_cut(graph, source_set, destination_set, |edge| {
+        capacities[edge] as u64
+    })
+}
+
+/// Capacity of each edge of the graph by its index, as read from its payload by `capacity_fn`.
+pub(crate) fn edge_capacities<G, F>(graph: G, capacity_fn: F) -> Vec<usize>
+where
+    G: EdgeIndexable + IntoEdgeReferences,
+    F: Fn(&G::EdgeWeight) -> usize,
+{
+    let mut capacities = vec![0; graph.edge_bound()];
+    for edge in graph.edge_references() {
+        capacities[EdgeIndexable::to_index(&graph, edge.id())] = capacity_fn(edge.weight());
+    }
+    capacities
+}
+
+/// Value of the maximum flow between the source and destination sets, which equals the size of a
+/// minimum cut between them. Panics if one of the sets is empty or the sets overlap, see
+/// [`try_max_flow_between_sets`].
+pub fn max_flow_between_sets<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> usize
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_max_flow_between_sets(graph, source_set, destination_set)
+        .unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Same as [`max_flow_between_sets`], but returns an error instead of panicking if one of the
+/// sets is empty or the sets overlap.
+pub fn try_max_flow_between_sets<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> Result<usize, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    validate_sets(&source_set, &destination_set)?;
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+    Ok(max_flow_value(
+        &compacted_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+    ))
+}
+
+/// Value of the maximum flow between two vertices together with a minimum cut between them, in
+/// the spirit of petgraph's `ford_fulkerson`, which returns the flow value with the flow of each
+/// edge. The cut is the one of [`minimum_cut`], so its size is the flow value. If the vertices
+/// are not connected, the flow is zero and the cut has no edges, with the vertices reachable from
+/// the source on its source side and the destination and the other vertices with edges on its
+/// destination side.
+/// Panics if the source is the destination.
+///
+/// ```
+/// use important_separators::cuts::max_flow_min_cut;
+/// use petgraph::graph::UnGraph;
+///
+/// // 0 - 1 = 2, where 1 and 2 are joined by two edges
+/// let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 2)]);
+///
+/// let (flow, cut) = max_flow_min_cut(&graph, 0, 2);
+/// assert_eq!(1, flow);
+/// assert_eq!(vec![0], cut.cut_edge_set);
+/// ```
+pub fn max_flow_min_cut<G>(graph: G, source: usize, destination: usize) -> (usize, Cut)
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    if let Some(cut) = minimum_cut(graph, vec![source], vec![destination]) {
+        return (cut.size, cut);
+    }
+
+    let mut source_side = reachable_vertices(graph, &[source], |_| true)
+        .into_iter()
+        .collect::<Vec<_>>();
+    source_side.sort_unstable();
+    let mut destination_side = graph
+        .edge_references()
+        .flat_map(|edge| [edge.source(), edge.target()])
+        .map(|vertex| NodeIndexable::to_index(&graph, vertex))
+        .chain([destination])
+        .filter(|vertex| !source_side.contains(vertex))
+        .collect::<Vec<_>>();
+    destination_side.sort_unstable();
+    destination_side.dedup();
+    (0, Cut::new(source_side, destination_side, vec![]))
+}
+
+/// Flow on each edge of a maximum flow between two vertices, indexed by the edge indices of the
+/// given graph like the second value returned by petgraph's `ford_fulkerson`. As all capacities
+/// are one, each edge carries a flow of either zero or one, which is one exactly for the edges of
+/// the augmenting paths, see [`augmenting_paths`](crate::cuts::augmenting_paths). Panics if the
+/// source is the destination.
+pub fn max_flow_per_edge<G>(graph: G, source: usize, destination: usize) -> Vec<usize>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut flows = vec![0; EdgeIndexable::edge_bound(&graph)];
+    let edge_count = graph.edge_references().count();
+    for path in augmenting_paths(graph, source, destination, edge_count).unwrap_or_default() {
+        for edge in path.edges {
+            flows[edge] += 1;
+        }
+    }
+    flows
+}
+
+/// Edge connectivity between two vertices, i.e. the largest number of edge-disjoint paths between
+/// them, which equals the value of the maximum flow with unit capacities.
+pub fn edge_connectivity<G>(graph: G, source: usize, destination: usize) -> usize
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    max_flow_between_sets(graph, vec![source], vec![destination])
+}
+
+/// Value of the maximum flow between the source and destination sets of a graph with contiguous
+/// indices, i.e. the size of a minimum cut between them. Only the value is needed, so the flow is
+/// pushed from the whole source set at once instead of contracting the sets first.
+pub(crate) fn max_flow_value(
+    graph: &UnGraph,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+) -> usize {
+    max_flow_value_between_sets(graph, &source_set, &destination_set)
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::algo::ford_fulkerson;
+    use petgraph::graph::{DiGraph, UnGraph};
+    use petgraph::stable_graph::{NodeIndex, StableUnGraph};
+    use petgraph::visit::EdgeRef;
+    use petgraph::{Graph, Undirected};
+
+    use crate::cuts::cut::Cut;
+    use crate::cuts::minimum_cut::{
+        edge_connectivity, explained_minimum_cut, extreme_minimum_cuts, max_flow_between_sets,
+        max_flow_min_cut, max_flow_per_edge, minimum_cardinality_cuts, minimum_cut,
+        minimum_cut_closest_to_source, minimum_cut_with_capacities, minimum_weight_cut,
+        try_explained_minimum_cut, try_extreme_minimum_cuts, try_minimum_cardinality_cuts,
+        try_minimum_cut_closest_to_source, try_minimum_weight_cut,
+    };
+    use crate::Error;
+
+    #[test]
+    fn minimum_cut_closest_to_destination() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)]);
+
+        let cut = minimum_cut(&graph, vec![0], vec![4]).unwrap();
+        assert_eq!(vec![4], cut.cut_edge_set);
+        assert_eq!(vec![4], cut.destination_set);
+
+        let cut = minimum_cut(&graph, vec![0], vec![3]).unwrap();
+        let mut cut_edges = cut.cut_edge_set;
+        cut_edges.sort();
+        assert_eq!(vec![2, 3], cut_edges);
+    }
+
+    #[test]
+    fn each_cut_edge_blocks_one_path() {
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 4),
+            (3, 5),
+            (4, 7),
+            (5, 8),
+            (7, 10),
+            (8, 10),
+            (6, 10),
+            (6, 9),
+            (9, 10),
+        ]);
+
+        let explained = explained_minimum_cut(&graph, vec![0, 1, 2], vec![9, 10]).unwrap();
+        let mut cut_edges = explained.cut.cut_edge_set.clone();
+        cut_edges.sort();
+        assert_eq!(vec![10, 11], cut_edges);
+        assert_eq!(2, explained.paths.len());
+
+        let mut blocked = vec![];
+        for edge in cut_edges {
+            let path_indices = &explained.blocked_paths[&edge];
+            assert_eq!(1, path_indices.len());
+            assert!(explained.paths[path_indices[0]].edges.contains(&edge));
+            blocked.push(path_indices[0]);
+        }
+        blocked.sort();
+        assert_eq!(vec![0, 1], blocked);
+    }
+
+    #[test]
+    fn isolated_destination_is_on_destination_side() {
+        let mut graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 4),
+            (3, 5),
+            (4, 7),
+            (5, 8),
+            (7, 10),
+            (8, 10),
+            (6, 10),
+            (6, 9),
+            (9, 10),
+        ]);
+        while graph.node_count() < 100 {
+            graph.add_node(());
+        }
+
+        let cut = minimum_cut(&graph, vec![0], vec![6, 99]).unwrap();
+        assert!(cut.destination_set.contains(&6));
+        assert!(cut.destination_set.contains(&99));
+        assert!(!cut.source_set.contains(&99));
+        assert_eq!(2, cut.size);
+    }
+
+    #[test]
+    fn max_flow_of_sets() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 6)]);
+
+        assert_eq!(1, max_flow_between_sets(&graph, vec![0], vec![4]));
+        assert_eq!(2, max_flow_between_sets(&graph, vec![0], vec![3]));
+        assert_eq!(3, max_flow_between_sets(&graph, vec![0, 4], vec![3]));
+        assert_eq!(0, max_flow_between_sets(&graph, vec![0], vec![6]));
+    }
+
+    #[test]
+    fn max_flow_matches_ford_fulkerson() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (1, 2)]);
+        // petgraph's ford_fulkerson works on directed graphs, so each edge becomes two arcs
+        let mut network = DiGraph::<(), u32>::new();
+        graph.node_indices().for_each(|_| {
+            network.add_node(());
+        });
+        for edge in graph.edge_references() {
+            network.add_edge(edge.source(), edge.target(), 1);
+            network.add_edge(edge.target(), edge.source(), 1);
+        }
+
+        for (source, destination) in [(0, 3), (0, 4), (1, 2)] {
+            let (flow, cut) = max_flow_min_cut(&graph, source, destination);
+            let (expected_flow, _) = ford_fulkerson(
+                &network,
+                NodeIndex::new(source),
+                NodeIndex::new(destination),
+            );
+            assert_eq!(expected_flow as usize, flow);
+            assert_eq!(flow, cut.size);
+        }
+    }
+
+    #[test]
+    fn max_flow_min_cut_of_disconnected_vertices() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (2, 3)]);
+
+        let (flow, cut) = max_flow_min_cut(&graph, 0, 3);
+        assert_eq!(0, flow);
+        assert_eq!(Cut::new(vec![0, 1], vec![2, 3], vec![]), cut);
+    }
+
+    #[test]
+    fn every_edge_of_line_carries_flow() {
+        // 0 - 1 - 2 - 3, where 3 - 4 is not on the way
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
+
+        assert_eq!(vec![1, 1, 1, 0], max_flow_per_edge(&graph, 0, 3));
+        assert_eq!(vec![1, 1, 1, 1], max_flow_per_edge(&graph, 0, 4));
+    }
+
+    #[test]
+    fn three_edge_connected_vertices() {
+        // 0 and 5 are joined by three internally disjoint paths, and 5 has an extra neighbor
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (1, 5),
+            (0, 2),
+            (2, 5),
+            (0, 3),
+            (3, 4),
+            (4, 5),
+            (1, 2),
+            (5, 6),
+        ]);
+
+        assert_eq!(3, edge_connectivity(&graph, 0, 5));
+        assert_eq!(3, edge_connectivity(&graph, 5, 0));
+        assert_eq!(1, edge_connectivity(&graph, 0, 6));
+    }
+
+    #[test]
+    fn minimum_cut_of_disconnected_sets() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (2, 3)]);
+        assert!(minimum_cut(&graph, vec![0], vec![3]).is_none());
+    }
+
+    #[test]
+    fn minimum_cut_on_stable_graph_with_removed_node() {
+        // 0 - 2 - 3 - 4 after removing vertex 1 together with edges 0 and 1
+        let mut graph =
+            StableUnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (0, 2)]);
+        graph.remove_node(NodeIndex::new(1));
+
+        let cut = minimum_cut(&graph, vec![0], vec![4]).unwrap();
+        let mut source_set = cut.source_set;
+        source_set.sort();
+        assert_eq!(vec![0, 2, 3], source_set);
+        assert_eq!(vec![4], cut.destination_set);
+        assert_eq!(vec![3], cut.cut_edge_set);
+    }
+
+    #[test]
+    fn extreme_minimum_cuts_differ() {
+        // 0 - 1 - 2 - 3
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+
+        let (closest_to_source, closest_to_destination) =
+            extreme_minimum_cuts(&graph, vec![0], vec![3], 1).unwrap();
+        assert_eq!(vec![0], closest_to_source.cut_edge_set);
+        assert_eq!(vec![0], closest_to_source.source_set);
+        assert_eq!(vec![2], closest_to_destination.cut_edge_set);
+        assert_eq!(vec![3], closest_to_destination.destination_set);
+        assert_ne!(closest_to_source, closest_to_destination);
+
+        assert!(extreme_minimum_cuts(&graph, vec![0], vec![3], 0).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "Vertex 1 is in both the source and the destination set")]
+    fn extreme_minimum_cuts_reject_overlapping_sets() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        extreme_minimum_cuts(&graph, vec![0, 1], vec![1, 2], 2);
+    }
+
+    #[test]
+    fn try_variants_reject_invalid_sets() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+
+        assert!(matches!(
+            try_explained_minimum_cut(&graph, vec![], vec![2]),
+            Err(Error::EmptySet)
+        ));
+        assert_eq!(
+            Err(Error::OverlappingSets(1)),
+            try_minimum_cut_closest_to_source(&graph, vec![0, 1], vec![1, 2])
+        );
+        assert_eq!(
+            Err(Error::OverlappingSets(1)),
+            try_extreme_minimum_cuts(&graph, vec![0, 1], vec![1, 2], 2)
+        );
+        assert_eq!(
+            Err(Error::EmptySet),
+            try_minimum_cardinality_cuts(&graph, vec![0], vec![])
+        );
+        assert_eq!(
+            Ok(vec![1]),
+            try_extreme_minimum_cuts(&graph, vec![0], vec![2], 1)
+                .map(|cuts| cuts.unwrap().1.cut_edge_set)
+        );
+    }
+
+    #[test]
+    fn extreme_minimum_cuts_of_unique_minimum_cut() {
+        // 0 = 1 - 2 = 3, where = are two parallel paths
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 4),
+            (4, 1),
+            (0, 5),
+            (5, 1),
+            (1, 2),
+            (2, 6),
+            (6, 3),
+            (2, 7),
+            (7, 3),
+        ]);
+
+        let (closest_to_source, closest_to_destination) =
+            extreme_minimum_cuts(&graph, vec![0], vec![3], 1).unwrap();
+        assert_eq!(vec![4], closest_to_source.cut_edge_set);
+        assert_eq!(
+            closest_to_source.cut_edge_set,
+            closest_to_destination.cut_edge_set
+        );
+    }
+
+    #[test]
+    fn minimum_weight_cut_differs_from_minimum_cut() {
+        //          -2-
+        //   5     /   \
+        // 0 --- 1      4
+        //         \   /
+        //          -3-
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3), (2, 4), (3, 4)]);
+        let weights = [5, 1, 1, 1, 1];
+
+        let cut = minimum_cut(&graph, vec![0], vec![4]).unwrap();
+        assert_eq!(vec![0], cut.cut_edge_set);
+        assert_eq!(5, cut.weight(|edge| weights[edge]));
+
+        let (weighted_cut, weight) =
+            minimum_weight_cut(&graph, vec![0], vec![4], |edge| weights[edge]).unwrap();
+        let mut cut_edges = weighted_cut.cut_edge_set.clone();
+        cut_edges.sort();
+        assert_eq!(vec![3, 4], cut_edges);
+        assert_eq!(2, weighted_cut.size);
+        assert_eq!(2, weight);
+    }
+
+    #[test]
+    fn capacities_are_read_from_edge_weights() {
+        let graph = Graph::<(), u32, Undirected>::from_edges([
+            (0, 1, 5),
+            (1, 2, 1),
+            (1, 3, 1),
+            (2, 4, 1),
+            (3, 4, 1),
+        ]);
+
+        assert_eq!(
+            vec![0],
+            minimum_cut(&graph, vec![0], vec![4]).unwrap().cut_edge_set
+        );
+
+        let (cut, capacity) =
+            minimum_cut_with_capacities(&graph, vec![0], vec![4], |&weight| weight as usize)
+                .unwrap();
+        let mut cut_edges = cut.cut_edge_set;
+        cut_edges.sort();
+        assert_eq!(vec![3, 4], cut_edges);
+        assert_eq!(2, capacity);
+    }
+
+    #[test]
+    fn minimum_cut_closest_to_source_and_destination() {
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 4),
+            (3, 5),
+            (4, 7),
+            (5, 6),
+            (6, 7),
+        ]);
+
+        let mut source_cut = minimum_cut_closest_to_source(&graph, vec![0], vec![7]).unwrap();
+        source_cut.source_set.sort();
+        source_cut.cut_edge_set.sort();
+        assert_eq!(vec![0, 1, 2, 3, 4], source_cut.source_set);
+        assert_eq!(vec![7, 8], source_cut.cut_edge_set);
+
+        let mut destination_cut = minimum_cut(&graph, vec![0], vec![7]).unwrap();
+        destination_cut.cut_edge_set.sort();
+        assert_eq!(vec![7], destination_cut.destination_set);
+        assert_eq!(vec![8, 10], destination_cut.cut_edge_set);
+    }
+
+    #[test]
+    fn all_minimum_cardinality_cuts() {
+        // two pairs of parallel edges in a row, each of which is a minimum cut
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (1, 2), (1, 2)]);
+
+        let cuts = minimum_cardinality_cuts(&graph, vec![0], vec![2]);
+        assert_eq!(2, cuts.len());
+        assert_eq!(vec![0, 1], cuts[0].cut_edge_set);
+        assert_eq!(vec![0], cuts[0].source_set);
+        assert_eq!(vec![2, 3], cuts[1].cut_edge_set);
+        assert_eq!(vec![0, 1], cuts[1].source_set);
+        assert!(cuts.iter().all(|cut| cut.size == 2));
+
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (3, 4)]);
+        let cuts = minimum_cardinality_cuts(&graph, vec![0], vec![2]);
+        assert_eq!(2, cuts.len());
+        assert_eq!(vec![0, 3, 4], cuts[0].source_set);
+
+        // the sets are not connected, so the empty cut is the only minimum cut
+        let cuts = minimum_cardinality_cuts(&graph, vec![0], vec![4]);
+        assert_eq!(vec![Cut::new(vec![0, 1, 2], vec![3, 4], vec![])], cuts);
+    }
+
+    #[test]
+    fn large_weights_are_not_expanded() {
+        // 0 = 1 - 2 - 3, where cutting the parallel edges or the last edge weighs the same
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (1, 2), (2, 3)]);
+        let weights = [1 << 40, 1 << 40, (1 << 41) + 1, 1 << 41];
+
+        let (cut, weight) =
+            minimum_weight_cut(&graph, vec![0], vec![3], |edge| weights[edge]).unwrap();
+        assert_eq!(vec![3], cut.cut_edge_set);
+        assert_eq!(vec![3], cut.destination_set);
+        assert_eq!(1 << 41, weight);
+    }
+
+    #[test]
+    fn components_without_sets_do_not_multiply_cuts() {
+        let mut edges = vec![(0, 1), (0, 1), (1, 2), (1, 2)];
+        edges.extend((0..16).map(|i| (3 + 2 * i, 4 + 2 * i)));
+        let graph = UnGraph::<(), ()>::from_edges(edges);
+
+        let cuts = minimum_cardinality_cuts(&graph, vec![0], vec![2]);
+        assert_eq!(2, cuts.len());
+        assert!(cuts
+            .iter()
+            .all(|cut| cut.destination_set == vec![2] || cut.destination_set == vec![1, 2]));
+    }
+
+    #[test]
+    fn overflowing_weights() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (1, 2)]);
+        let weights = [u64::MAX - 1, u64::MAX - 1, 1];
+
+        assert_eq!(
+            Err(Error::Overflow),
+            try_minimum_weight_cut(&graph, vec![0], vec![2], |edge| weights[edge])
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use itertools::Itertools;
+use petgraph::visit::{EdgeIndexable, IntoEdgeReferences, NodeIndexable};
+
+use crate::cuts::compaction::Compaction;
+use crate::cuts::cut::{generate_minimum_cut_closest_to_source, map_cut_to_original};
+use crate::cuts::path_residual::get_augmenting_paths_and_residual_graph_for_sets;
+
+/// Computes a multiway cut of at most k edges that separates every terminal from all the other
+/// terminals. Returns the sorted edge indices of the cut, or `None` if the cut has more than k
+/// edges.
+///
+/// Finding a minimum multiway cut is NP-hard for three or more terminals, so we use the standard
+/// reduction to pairwise minimum cuts: for every terminal, we find a minimum cut between it and
+/// all other terminals, and then combine all of these isolating cuts except the largest one. The
+/// result is at most `2 - 2 / t` times the size of a minimum multiway cut for `t` terminals, and
+/// a minimum multiway cut for two terminals.
+pub fn multiway_cut<G>(graph: G, terminals: Vec<usize>, k: usize) -> Option<Vec<usize>>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let (compacted_graph, compaction) = Compaction::new(graph, &terminals);
+    let terminals = compaction.compact_vertices(&terminals.into_iter().unique().collect_vec());
+    let edges_in_use = vec![true; compacted_graph.edge_count()];
+
+    let mut isolating_cuts = terminals
+        .iter()
+        .map(|&terminal| {
+            let other_terminals = terminals
+                .iter()
+                .copied()
+                .filter(|&other| other != terminal)
+                .collect_vec();
+            if other_terminals.is_empty() {
+                return vec![];
+            }
+            // we take the cut closest to the terminal to keep the isolated region small
+            match get_augmenting_paths_and_residual_graph_for_sets(
+                &compacted_graph,
+                vec![terminal],
+                other_terminals,
+                compacted_graph.edge_count(),
+                &edges_in_use,
+            ) {
+                Some((paths, residual, index_mapping)) => {
+                    let cut = generate_minimum_cut_closest_to_source(&paths, &residual);
+                    map_cut_to_original(cut, &index_mapping)
+                        .expect("The index mapping should belong to the contracted graph")
+                        .cut_edge_set
+                }
+                // the terminal is already separated from the others
+                None => vec![],
+            }
+        })
+        .collect_vec();
+
+    // the largest isolating cut is not needed, as its terminal is separated by the other cuts
+    if let Some((largest, _)) = isolating_cuts
+        .iter()
+        .enumerate()
+        .max_by_key(|(_, cut)| cut.len())
+    {
+        isolating_cuts.remove(largest);
+    }
+
+    let cut_edges = isolating_cuts
+        .into_iter()
+        .flatten()
+        .unique()
+        .sorted()
+        .collect_vec();
+    if cut_edges.len() > k {
+        return None;
+    }
+    Some(
+        compaction
+            .original_edges(&cut_edges)
+            .into_iter()
+            .sorted()
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use crate::cuts::multiway::multiway_cut;
+    use crate::cuts::verify::reachable_vertices;
+
+    #[test]
+    fn star_with_three_terminal_leaves() {
+        // center 0 with leaves 1 to 4, of which 1, 2 and 3 are terminals
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (0, 3), (0, 4)]);
+        let terminals = vec![1, 2, 3];
+
+        let cut = multiway_cut(&graph, terminals.clone(), 3).unwrap();
+        assert_eq!(2, cut.len());
+        assert!(cut.iter().all(|edge| [0, 1, 2].contains(edge)));
+        for &terminal in &terminals {
+            let reachable = reachable_vertices(&graph, &[terminal], |edge| !cut.contains(&edge));
+            assert!(terminals
+                .iter()
+                .all(|&other| other == terminal || !reachable.contains(&other)));
+        }
+
+        assert!(multiway_cut(&graph, terminals, 1).is_none());
+    }
+
+    #[test]
+    fn two_terminals_give_minimum_cut() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3), (2, 4), (3, 4)]);
+
+        assert_eq!(Some(vec![0]), multiway_cut(&graph, vec![0, 4], 2));
+    }
+}
@@ -1,12 +1,18 @@
+use crate::cuts::cut::map_cut_to_original;
+use crate::cuts::path_residual::create_contracted_graph;
 use crate::cuts::Cut;
-use petgraph::prelude::Bfs;
+use petgraph::graph::NodeIndex;
 use petgraph::visit::{
-    EdgeIndexable, EdgeRef, IntoEdges, IntoNeighbors, IntoNodeReferences, NodeCount, NodeIndexable,
-    Visitable,
+    EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNodeReferences,
+    NodeCount, NodeIndexable, Visitable,
 };
 
-/// Get cuts between `source` and `destination` of size at most `k`
-#[allow(dead_code)]
+/// Get cuts between `source` and `destination` of size at most `k` by brute force.
+///
+/// Every connected set of vertices that contains the source and not the destination is the source
+/// side of a cut, which consists of the edges leaving it. As all such sets are enumerated, this
+/// takes time exponential in the number of vertices and is only meant as a baseline for small
+/// graphs. The vertex and edge sets of the returned cuts are sorted.
 pub fn generate_cuts<G>(graph: G, source: G::NodeId, destination: G::NodeId, k: usize) -> Vec<Cut>
 where
     G: EdgeIndexable
@@ -19,25 +25,42 @@ where
 {
     let mut ret: Vec<Cut> = vec![];
 
-    // TODO Consider improving used data structure
-    let mut visited: Vec<usize> = vec![];
+    let mut vertices = graph
+        .node_identifiers()
+        .map(|node| NodeIndexable::to_index(&graph, node))
+        .collect::<Vec<_>>();
+    vertices.sort();
+    let source_index = NodeIndexable::to_index(&graph, source);
+    let destination_index = NodeIndexable::to_index(&graph, destination);
+    let others = vertices
+        .iter()
+        .copied()
+        .filter(|&v| v != source_index && v != destination_index)
+        .collect::<Vec<_>>();
 
-    // Traverse nodes using BFS
-    let mut bfs = Bfs::new(&graph, source);
-    while let Some(node) = bfs.next(&graph) {
-        if node != destination {
-            // never mark the destination as visited
-            visited.push(NodeIndexable::to_index(&graph, node));
+    for subset in 0usize..1 << others.len() {
+        let mut source_set = others
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| subset & (1 << i) != 0)
+            .map(|(_, &v)| v)
+            .chain([source_index])
+            .collect::<Vec<_>>();
+        source_set.sort();
+
+        // the source side has to be connected, as the cut edges would not be minimal otherwise
+        if !is_connected(graph, &source_set, source) {
+            continue;
         }
 
         let mut cut_edges: Vec<usize> = vec![];
-        // TODO Maybe we don't have to go through every edge of every node here?
-        for &visited_node in visited.iter() {
-            for edge in graph.edges(NodeIndexable::from_index(&graph, visited_node)) {
+        for &vertex in &source_set {
+            for edge in graph.edges(NodeIndexable::from_index(&graph, vertex)) {
                 let edge_source_id = NodeIndexable::to_index(&graph, edge.source());
                 let edge_target_id = NodeIndexable::to_index(&graph, edge.target());
-                // We add the edge to the cut if one of its endpoints is visited and the other is not
-                if visited.contains(&edge_source_id) ^ visited.contains(&edge_target_id) {
+                // We add the edge to the cut if one of its endpoints is in the source set and the
+                // other is not
+                if source_set.contains(&edge_source_id) ^ source_set.contains(&edge_target_id) {
                     let edge_id = EdgeIndexable::to_index(&graph, edge.id());
                     if !cut_edges.contains(&edge_id) {
                         cut_edges.push(edge_id);
@@ -45,30 +68,163 @@ where
                 }
             }
         }
+        cut_edges.sort();
 
         if cut_edges.len() <= k {
-            let dest_set = (0usize..graph.node_count())
-                .filter(|n| !visited.contains(&n))
+            let dest_set = vertices
+                .iter()
+                .copied()
+                .filter(|v| !source_set.contains(v))
                 .collect();
-            let cut = Cut::new(visited.clone(), dest_set, cut_edges);
-            if !ret.contains(&cut) {
-                ret.push(cut);
-            }
+            ret.push(Cut::new(source_set, dest_set, cut_edges));
         }
     }
 
     ret
 }
 
-#[allow(dead_code)]
-pub fn filter_important_cuts(cuts: &Vec<Cut>) -> Vec<Cut> {
-    // TODO Consider writing this a bit nicer using combinations or something similar
+/// Get cuts between the source set and the destination set of size at most `k` by brute force.
+///
+/// The sets are contracted into single vertices before enumerating the cuts with
+/// [`generate_cuts`], and the cuts are then mapped back to the vertices and edges of the given
+/// graph. Vertices that are not incident to any edge are left out of both sides of the cuts,
+/// unless they are in one of the sets.
+pub fn generate_cuts_for_sets<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Vec<Cut>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let (contracted_graph, source, destination, index_mapping) =
+        create_contracted_graph(graph, source_set, destination_set);
+
+    // parallel edges are merged into one contracted edge, so the size of a cut is only known once
+    // it is mapped back
+    generate_cuts(
+        &contracted_graph,
+        NodeIndex::new(source),
+        NodeIndex::new(destination),
+        contracted_graph.edge_count(),
+    )
+    .into_iter()
+    .map(|cut| {
+        let mut cut = map_cut_to_original(cut, &index_mapping)
+            .expect("The index mapping should belong to the contracted graph");
+        cut.source_set.sort();
+        cut.destination_set.sort();
+        cut.cut_edge_set.sort();
+        cut
+    })
+    .filter(|cut| cut.size <= k)
+    .collect()
+}
+
+/// Whether the vertices induce a connected subgraph, checked by a traversal from `start` that
+/// stays within them.
+fn is_connected<G>(graph: G, vertices: &[usize], start: G::NodeId) -> bool
+where
+    G: NodeIndexable + IntoNeighbors,
+{
+    let mut visited = vec![NodeIndexable::to_index(&graph, start)];
+    let mut stack = vec![start];
+    while let Some(node) = stack.pop() {
+        for neighbor in graph.neighbors(node) {
+            let neighbor_index = NodeIndexable::to_index(&graph, neighbor);
+            if vertices.contains(&neighbor_index) && !visited.contains(&neighbor_index) {
+                visited.push(neighbor_index);
+                stack.push(neighbor);
+            }
+        }
+    }
+    visited.len() == vertices.len()
+}
+
+/// Keep the cuts that are important among the given ones, i.e. those for which no other cut of at
+/// most the same size has a source set that is a strict superset of theirs.
+pub fn filter_important_cuts(cuts: &[Cut]) -> Vec<Cut> {
     cuts.iter()
         .filter(|&cut_i| {
-            cuts.iter().any(|cut_j| {
-                cut_j.size <= cut_i.size && cut_j.source_set.len() < cut_i.source_set.len()
+            !cuts.iter().any(|cut_j| {
+                cut_j.size <= cut_i.size
+                    && cut_j.source_set.len() > cut_i.source_set.len()
+                    && cut_i
+                        .source_set
+                        .iter()
+                        .all(|v| cut_j.source_set.contains(v))
             })
         })
-        .map(|c| c.clone())
+        .cloned()
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::{NodeIndex, UnGraph};
+
+    use crate::cuts::{
+        filter_important_cuts, generate_cuts, generate_cuts_for_sets, max_flow_between_sets,
+    };
+
+    #[test]
+    fn cuts_of_line() {
+        // 0 - 1 - 2 - 3
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+
+        let cuts = generate_cuts(&graph, NodeIndex::new(0), NodeIndex::new(3), 1);
+        let cut_edges = cuts
+            .iter()
+            .map(|cut| cut.cut_edge_set.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![vec![0], vec![1], vec![2]], cut_edges);
+
+        let important = filter_important_cuts(&cuts);
+        assert_eq!(1, important.len());
+        assert_eq!(vec![0, 1, 2], important[0].source_set);
+        assert_eq!(vec![2], important[0].cut_edge_set);
+    }
+
+    #[test]
+    fn cuts_for_sets_match_flow() {
+        /* Visualization of the graph used
+           -1-          9---6
+          / | \         |  /
+         /  |  \        | /
+        0---2---4---7---10
+         \  |          /
+          \ |         /
+           -3---5---8-
+        */
+        let graph = UnGraph::<(), ()>::from_edges([
+            (0, 1),
+            (0, 2),
+            (0, 3),
+            (1, 2),
+            (2, 3),
+            (1, 4),
+            (2, 4),
+            (3, 5),
+            (4, 7),
+            (5, 8),
+            (7, 10),
+            (8, 10),
+            (6, 10),
+            (6, 9),
+            (9, 10),
+        ]);
+        let source_set = vec![0, 1, 2];
+        let destination_set = vec![9, 10];
+
+        let cuts = generate_cuts_for_sets(&graph, source_set.clone(), destination_set.clone(), 3);
+        let min_cut_size = cuts.iter().map(|cut| cut.size).min().unwrap();
+        assert_eq!(
+            max_flow_between_sets(&graph, source_set, destination_set),
+            min_cut_size
+        );
+        assert!(cuts.iter().all(|cut| cut.size <= 3));
+        // the two parallel edges (1, 4) and (2, 4) of the contracted graph are both in the cut
+        assert!(cuts.iter().any(|cut| cut.cut_edge_set == vec![5, 6, 7]));
+    }
+}
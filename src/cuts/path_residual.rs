@@ -1,24 +1,39 @@
 use std::cmp::{max, min};
 use std::collections::HashMap;
-use std::collections::VecDeque;
 
-use petgraph::graph::NodeIndex;
+use petgraph::graph::{EdgeIndex, NodeIndex};
 use petgraph::visit::{
     EdgeCount, EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoEdges, NodeCount, NodeIndexable,
-    VisitMap, Visitable,
+    Visitable,
 };
 use petgraph::{Directed, Graph, Undirected};
 
-// Based on petgraph::algo::ford_fulkerson
+use crate::cuts::compaction::Compaction;
+use crate::cuts::flow::{
+    decompose_flow, flow_direction, has_augmenting_path, other_endpoint, residual_capacity,
+};
+use crate::Error;
 
-#[derive(Debug)]
+/// A path given by its vertices and edges, in order from the source to the destination
+#[derive(Debug, Clone)]
 pub struct Path {
     pub vertices: Vec<usize>,
     pub edges: Vec<usize>,
 }
 
 impl Path {
-    pub fn get_destination(paths: &Vec<Path>) -> usize {
+    /// Creates a path from its vertices and the edges between them, so there has to be one vertex
+    /// more than there are edges.
+    pub fn new(vertices: Vec<usize>, edges: Vec<usize>) -> Self {
+        debug_assert_eq!(
+            vertices.len(),
+            edges.len() + 1,
+            "A path should have one vertex more than it has edges"
+        );
+        Self { vertices, edges }
+    }
+
+    pub fn get_destination(paths: &[Path]) -> usize {
         *paths
             .first()
             .expect("Paths should be nonempty")
@@ -26,7 +41,7 @@ impl Path {
             .last()
             .expect("The vertices of a path cannot be empty")
     }
-    pub fn get_source(paths: &Vec<Path>) -> usize {
+    pub fn get_source(paths: &[Path]) -> usize {
         *paths
             .first()
             .expect("Paths should be nonempty")
@@ -35,23 +50,70 @@ impl Path {
             .expect("The vertices of a path cannot be empty")
     }
 
-    pub fn get_destination_node_index(paths: &Vec<Path>) -> NodeIndex<usize> {
-        NodeIndex::from(Path::get_destination(&paths))
+    pub fn get_destination_node_index(paths: &[Path]) -> NodeIndex<usize> {
+        NodeIndex::from(Path::get_destination(paths))
+    }
+
+    pub fn get_source_node_index(paths: &[Path]) -> NodeIndex<usize> {
+        NodeIndex::from(Path::get_source(paths))
+    }
+
+    /// Number of edges on the path.
+    pub fn hop_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// First vertex of the path.
+    pub fn source(&self) -> usize {
+        *self
+            .vertices
+            .first()
+            .expect("The vertices of a path cannot be empty")
+    }
+
+    /// Last vertex of the path.
+    pub fn destination(&self) -> usize {
+        *self
+            .vertices
+            .last()
+            .expect("The vertices of a path cannot be empty")
     }
 
-    pub fn get_source_node_index(paths: &Vec<Path>) -> NodeIndex<usize> {
-        NodeIndex::from(Path::get_source(&paths))
+    /// Vertices of the original graph that the destination of the path, a vertex of the contracted
+    /// graph, was contracted from, in increasing order. Empty if the mapping does not know the
+    /// destination.
+    pub fn get_original_destinations(&self, mapping: &IndexMapping) -> Vec<usize> {
+        let mut destinations = mapping
+            .vertex_contracted_to_original
+            .get(&self.destination())
+            .cloned()
+            .unwrap_or_default();
+        destinations.sort_unstable();
+        destinations
     }
 }
 
+/// The reverse of the residual graph of a maximum flow between a source and a destination.
+///
+/// Each edge that is used by an augmenting path gets a single arc pointing from the source towards
+/// the destination, i.e. against the direction of the residual arc. Every other edge gets an arc in
+/// both directions. The vertices that can be reached from the destination in this graph are
+/// exactly the destination side of the minimum cut closest to the destination.
 pub type ResidualGraph = Graph<(), (), Directed, usize>;
 
+/// An undirected graph with `usize` indices, which the algorithms work on internally. They accept
+/// graphs with any index type though, e.g. `Graph<(), (), Undirected, u32>` to save memory.
 pub type UnGraph = Graph<(), (), Undirected, usize>;
 
+/// Maps the vertices and edges of a contracted graph to the ones they were contracted from, with
+/// lookups in both directions. The mapping can only be read once it is built, so the lookups always
+/// agree with each other.
 #[derive(Debug)]
 pub struct IndexMapping {
-    pub vertex_contracted_to_original: HashMap<usize, Vec<usize>>,
-    pub edge_contracted_to_original: HashMap<usize, Vec<usize>>,
+    vertex_contracted_to_original: HashMap<usize, Vec<usize>>,
+    edge_contracted_to_original: HashMap<usize, Vec<usize>>,
+    vertex_original_to_contracted: HashMap<usize, usize>,
+    edge_original_to_contracted: HashMap<usize, usize>,
 }
 
 impl IndexMapping {
@@ -59,21 +121,70 @@ impl IndexMapping {
         Self {
             vertex_contracted_to_original: Default::default(),
             edge_contracted_to_original: Default::default(),
+            vertex_original_to_contracted: Default::default(),
+            edge_original_to_contracted: Default::default(),
         }
     }
 
-    #[allow(dead_code)]
     pub fn from(
         vertex_mapping: HashMap<usize, Vec<usize>>,
         edge_mapping: HashMap<usize, Vec<usize>>,
     ) -> Self {
+        let invert = |mapping: &HashMap<usize, Vec<usize>>| {
+            mapping
+                .iter()
+                .flat_map(|(&contracted, originals)| {
+                    originals
+                        .iter()
+                        .map(move |&original| (original, contracted))
+                })
+                .collect()
+        };
         Self {
+            vertex_original_to_contracted: invert(&vertex_mapping),
+            edge_original_to_contracted: invert(&edge_mapping),
             vertex_contracted_to_original: vertex_mapping,
             edge_contracted_to_original: edge_mapping,
         }
     }
 
+    /// Maps each contracted vertex to the vertices it was contracted from.
+    pub fn vertex_map(&self) -> &HashMap<usize, Vec<usize>> {
+        &self.vertex_contracted_to_original
+    }
+
+    /// Maps each contracted edge to the edges it was contracted from.
+    pub fn edge_map(&self) -> &HashMap<usize, Vec<usize>> {
+        &self.edge_contracted_to_original
+    }
+
+    /// Vertices the contracted vertex was contracted from, if it is in the mapping.
+    pub fn original_vertices(&self, contracted: usize) -> Option<&[usize]> {
+        self.vertex_contracted_to_original
+            .get(&contracted)
+            .map(Vec::as_slice)
+    }
+
+    /// Edges the contracted edge was contracted from, if it is in the mapping.
+    pub fn original_edges(&self, contracted: usize) -> Option<&[usize]> {
+        self.edge_contracted_to_original
+            .get(&contracted)
+            .map(Vec::as_slice)
+    }
+
+    /// Contracted vertex the original vertex was contracted into, if it is in the mapping.
+    pub fn contracted_vertex_of(&self, original: usize) -> Option<usize> {
+        self.vertex_original_to_contracted.get(&original).copied()
+    }
+
+    /// Contracted edge the original edge was contracted into, if it is in the mapping.
+    pub fn contracted_edge_of(&self, original: usize) -> Option<usize> {
+        self.edge_original_to_contracted.get(&original).copied()
+    }
+
     fn add_vertex(&mut self, contracted: usize, original: usize) {
+        self.vertex_original_to_contracted
+            .insert(original, contracted);
         match self.vertex_contracted_to_original.get(&contracted) {
             None => self
                 .vertex_contracted_to_original
@@ -91,6 +202,8 @@ impl IndexMapping {
     }
 
     fn add_edge(&mut self, contracted: usize, original: usize) {
+        self.edge_original_to_contracted
+            .insert(original, contracted);
         match self.edge_contracted_to_original.get(&contracted) {
             None => self
                 .edge_contracted_to_original
@@ -107,98 +220,17 @@ impl IndexMapping {
     }
 }
 
-/// Gets the other endpoint of graph edge, if any, otherwise panics.
-fn other_endpoint<G>(graph: G, edge: G::EdgeRef, vertex: G::NodeId) -> G::NodeId
-where
-    G: NodeIndexable + IntoEdges,
-{
-    if vertex == edge.source() {
-        edge.target()
-    } else if vertex == edge.target() {
-        edge.source()
-    } else {
-        let end_point = NodeIndexable::to_index(&graph, vertex);
-        panic!("Illegal endpoint {}", end_point);
-    }
-}
-
-fn has_augmenting_path<G>(
-    graph: G,
-    source: G::NodeId,
-    destination: G::NodeId,
-    next_edge: &mut [Option<G::EdgeRef>],
-    edge_capacities: &[usize],
-) -> bool
-where
-    G: NodeIndexable + EdgeIndexable + Visitable + IntoEdges,
-{
-    let mut visited = graph.visit_map();
-    let mut queue: VecDeque<G::NodeId> = VecDeque::new();
-    visited.visit(source);
-    queue.push_back(source);
-
-    // do a BFS through the graph
-    while let Some(vertex) = queue.pop_front() {
-        for edge in graph.edges(vertex) {
-            let next = other_endpoint(&graph, edge, vertex);
-            let edge_index: usize = EdgeIndexable::to_index(&graph, edge.id());
-            let edge_available = edge_capacities[edge_index] > 0;
-            if !visited.is_visited(&next) && edge_available {
-                next_edge[NodeIndexable::to_index(&graph, next)] = Some(edge);
-                if next == destination {
-                    // we've found an augmenting path
-                    return true;
-                }
-                visited.visit(next);
-                queue.push_back(next);
-            }
-        }
-    }
-
-    false
-}
-
-fn generate_initial_residual_graph<G>(graph: G) -> ResidualGraph
-where
-    G: IntoEdgeReferences + NodeIndexable,
-{
-    // we assume the input graph to not contain any lone vertices, hence we may generate the residual
-    // graph from only the edges
-    let mut residual_graph_edges = vec![];
-    for edge in graph.edge_references() {
-        let source_index = NodeIndexable::to_index(&graph, edge.source());
-        let target_index = NodeIndexable::to_index(&graph, edge.target());
-        residual_graph_edges.push((source_index, target_index, ()));
-        residual_graph_edges.push((target_index, source_index, ()));
-    }
-    Graph::from_edges(residual_graph_edges)
-}
-
-fn remove_edge_from_residual_graph(
-    residual_graph: &mut ResidualGraph,
-    source_index: usize,
-    target_index: usize,
-) {
-    let removed_edge =
-        residual_graph.find_edge(NodeIndex::from(source_index), NodeIndex::from(target_index));
-    match removed_edge {
-        None => panic!("Should always find an edge to remove in the residual graph"),
-        Some(removed_edge_index) => {
-            let _ = residual_graph.remove_edge(removed_edge_index);
-        }
-    }
-}
-
 /// Get augmenting paths and reverse residual graph of graph if there exists a minimum cut of size at most k
 ///
-/// The reverse residual graph is built such that each edge that is part of an s-t path points from the
-/// source to the destination. Every other edge gets two edges that point in both directions
+/// The paths are found by augmenting the flow along shortest paths in the residual graph, which
+/// may cancel flow of earlier paths, and are then decomposed into edge-disjoint paths. See
+/// [`ResidualGraph`] for how the reverse residual graph looks like.
 pub fn get_augmenting_paths_and_residual_graph<G>(
     graph: G,
     source: G::NodeId,
     destination: G::NodeId,
     k: usize,
-    initial_edge_capacities: &Vec<usize>,
+    initial_edge_capacities: &[usize],
 ) -> Option<(Vec<Path>, ResidualGraph)>
 where
     G: NodeIndexable
@@ -209,69 +241,190 @@ where
         + IntoEdges
         + IntoEdgeReferences,
 {
-    let mut next_edge = vec![None; graph.node_count()];
-    // we build the reverse of the residual graph as we use it to find the minimum cut closest
-    // to the target
-    let mut residual_graph_reverse = generate_initial_residual_graph(&graph);
-
-    let mut edge_capacities = initial_edge_capacities.clone();
+    // a flow from a vertex to itself is meaningless, and every vertex would be on its path
+    if source == destination {
+        return None;
+    }
 
-    let mut paths: Vec<Path> = vec![];
+    let mut next_edge = vec![None; graph.node_bound()];
+    let mut edge_flows = vec![0isize; initial_edge_capacities.len()];
+    let mut flow_value = 0;
 
-    while has_augmenting_path(
-        &graph,
-        source,
-        destination,
-        &mut next_edge,
-        &edge_capacities,
-    ) {
-        // get path corresponding to current state of `next_edge`
+    // stop as soon as the flow exceeds k, as we are not interested in larger cuts
+    while flow_value <= k
+        && has_augmenting_path(
+            &graph,
+            source,
+            destination,
+            &mut next_edge,
+            initial_edge_capacities,
+            &edge_flows,
+        )
+    {
+        // push one unit of flow along the path corresponding to current state of `next_edge`
         let mut vertex = destination;
         let mut vertex_index = NodeIndexable::to_index(&graph, vertex);
-        let mut path_vertices = vec![vertex_index];
-        let mut path_edges = vec![];
         while let Some(edge) = next_edge[vertex_index] {
-            // While traversing, save the indices of the edge for removing the correct edge from
-            // the residual graph. Our paths are saved from the destination to the source, hence
-            // the first index is the source and the second the target. Refer to docstring for how
-            // the residual graph will look like in the end.
-            let rm_edge_source_index = vertex_index;
+            let next_index = vertex_index;
             vertex = other_endpoint(&graph, edge, vertex);
             vertex_index = NodeIndexable::to_index(&graph, vertex);
-            let rm_edge_target_index = vertex_index;
-            // for each edge in the path, reduce its capacity by one
             let edge_index = EdgeIndexable::to_index(&graph, edge.id());
-            edge_capacities[edge_index] -= 1;
-            // add vertex and edge to path
-            path_vertices.push(vertex_index);
-            path_edges.push(edge_index);
-            // and adjust the reverse residual graph if the edge weight has gone to zero
-            if edge_capacities[edge_index] == 0 {
-                remove_edge_from_residual_graph(
-                    &mut residual_graph_reverse,
-                    rm_edge_source_index,
-                    rm_edge_target_index,
-                );
-            }
+            edge_flows[edge_index] += flow_direction(vertex_index, next_index);
         }
+        flow_value += 1;
+    }
 
-        // flip order of path vertices/edges to have them start from the source and add to paths
-        path_vertices = path_vertices.into_iter().rev().collect();
-        path_edges = path_edges.into_iter().rev().collect();
-        paths.push(Path {
-            vertices: path_vertices,
-            edges: path_edges,
-        });
+    if flow_value == 0 || flow_value > k {
+        return None;
     }
 
-    if !paths.is_empty() && paths.len() <= k {
-        Some((paths, residual_graph_reverse))
-    } else {
-        None
+    // The reverse residual graph has an arc from u to v for every residual arc from v to u. Parallel
+    // and antiparallel edges of the graph would give the same arc several times, so each arc is
+    // only added once
+    let mut residual_graph_reverse = ResidualGraph::with_capacity(graph.node_bound(), 0);
+    for _ in 0..graph.node_bound() {
+        residual_graph_reverse.add_node(());
     }
+    for edge in graph.edge_references() {
+        let edge_source = NodeIndexable::to_index(&graph, edge.source());
+        let edge_target = NodeIndexable::to_index(&graph, edge.target());
+        let edge_index = EdgeIndexable::to_index(&graph, edge.id());
+        let capacity = initial_edge_capacities[edge_index];
+        let flow = edge_flows[edge_index];
+        if residual_capacity(capacity, flow, edge_target, edge_source) > 0 {
+            residual_graph_reverse.update_edge(
+                NodeIndex::new(edge_source),
+                NodeIndex::new(edge_target),
+                (),
+            );
+        }
+        if residual_capacity(capacity, flow, edge_source, edge_target) > 0 {
+            residual_graph_reverse.update_edge(
+                NodeIndex::new(edge_target),
+                NodeIndex::new(edge_source),
+                (),
+            );
+        }
+    }
+
+    let paths = decompose_flow(
+        graph,
+        &[NodeIndexable::to_index(&graph, source)],
+        &[NodeIndexable::to_index(&graph, destination)],
+        flow_value,
+        &mut edge_flows,
+    );
+    Some((paths, residual_graph_reverse))
+}
+
+/// Finds edge-disjoint paths from the source to the destination that witness the maximum flow
+/// between them, if the flow is positive and at most k. Each path lists its vertices and edges
+/// from the source to the destination, using the indices of the given graph.
+///
+/// ```
+/// use important_separators::cuts::augmenting_paths;
+/// use petgraph::graph::UnGraph;
+///
+/// //   1 - 2
+/// //  /     \
+/// // 0 - 3 - 5
+/// //  \     /
+/// //   --4--
+/// let graph = UnGraph::<(), ()>::from_edges([
+///     (0, 1), (1, 2), (2, 5), (0, 3), (3, 5), (0, 4), (4, 5),
+/// ]);
+///
+/// let paths = augmenting_paths(&graph, 0, 5, 3).unwrap();
+/// let mut vertices = paths.into_iter().map(|path| path.vertices).collect::<Vec<_>>();
+/// vertices.sort();
+/// assert_eq!(vec![vec![0, 1, 2, 5], vec![0, 3, 5], vec![0, 4, 5]], vertices);
+///
+/// // the flow is larger than k
+/// assert!(augmenting_paths(&graph, 0, 5, 2).is_none());
+/// ```
+///
+/// Panics if the source is the destination, see [`try_augmenting_paths`] for a version that
+/// returns an error instead.
+pub fn augmenting_paths<G>(
+    graph: G,
+    source: usize,
+    destination: usize,
+    k: usize,
+) -> Option<Vec<Path>>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    try_augmenting_paths(graph, source, destination, k).unwrap_or_else(|error| panic!("{}", error))
 }
 
-fn create_contracted_graph<G>(
+/// Same as [`augmenting_paths`], but returns an error instead of panicking if the source is the
+/// destination.
+pub fn try_augmenting_paths<G>(
+    graph: G,
+    source: usize,
+    destination: usize,
+    k: usize,
+) -> Result<Option<Vec<Path>>, Error>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    if source == destination {
+        return Err(Error::SourceEqualsDestination(source));
+    }
+    let (compacted_graph, compaction) = Compaction::new(graph, &[source, destination]);
+    let compacted = compaction.compact_vertices(&[source, destination]);
+
+    Ok(get_augmenting_paths_and_residual_graph(
+        &compacted_graph,
+        NodeIndex::from(compacted[0]),
+        NodeIndex::from(compacted[1]),
+        k,
+        &vec![1; compacted_graph.edge_count()],
+    )
+    .map(|(paths, _)| {
+        paths
+            .into_iter()
+            .map(|path| Path {
+                vertices: compaction.original_vertices(&path.vertices),
+                edges: compaction.original_edges(&path.edges),
+            })
+            .collect()
+    }))
+}
+
+/// Computes the [`ResidualGraph`] of the maximum flow between the source and destination sets if
+/// the flow is positive and at most k, e.g. for selecting a minimum cut with a custom policy.
+///
+/// The flow is computed on a graph in which the source set and the destination set have been
+/// contracted into single vertices. The residual graph uses the indices of this contracted graph,
+/// and the returned [`IndexMapping`] maps them to the vertex and edge indices of the given graph.
+pub fn residual_graph<G>(
+    graph: G,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    k: usize,
+) -> Option<(ResidualGraph, IndexMapping)>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let (compacted_graph, compaction) = Compaction::new(
+        graph,
+        &[source_set.clone(), destination_set.clone()].concat(),
+    );
+
+    get_augmenting_paths_and_residual_graph_for_sets(
+        &compacted_graph,
+        compaction.compact_vertices(&source_set),
+        compaction.compact_vertices(&destination_set),
+        k,
+        &vec![true; compacted_graph.edge_count()],
+    )
+    .map(|(_, residual, index_mapping)| {
+        (residual, compaction.original_index_mapping(index_mapping))
+    })
+}
+
+pub(crate) fn create_contracted_graph<G>(
     original_graph: G,
     source_set: Vec<usize>,
     destination_set: Vec<usize>,
@@ -279,16 +432,58 @@ fn create_contracted_graph<G>(
 where
     G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
 {
-    fn transform_if_in_set(element: &mut usize, set: &Vec<usize>, target: usize) {
-        if set.contains(&element) {
-            *element = target;
-        }
+    assert!(!source_set.is_empty(), "Source set should be nonempty");
+    assert!(
+        !destination_set.is_empty(),
+        "Destination set should be nonempty"
+    );
+
+    let (contracted_graph, group_vertices, index_mapping) =
+        contract_vertex_groups(original_graph, &[source_set, destination_set]);
+    match group_vertices[..] {
+        [Some(s), Some(t)] => (contracted_graph, s, t, index_mapping),
+        _ => panic!("New edge source and target should always be in the index mapping"),
     }
+}
 
-    let &new_source = source_set.first().expect("Source set should be nonempty");
-    let &new_destination = destination_set
-        .first()
-        .expect("Destination set should be nonempty");
+/// Contracts each group of vertices into a single vertex, keeping one edge for each pair of
+/// contracted vertices that are connected and dropping the edges within a group. Vertices that are
+/// not in any group are kept as they are. The vertices of the contracted graph are numbered in the
+/// order in which their edges appear, and the returned mapping gives the vertices and edges of the
+/// input graph that each of them stands for, including the members of a group that are not
+/// incident to any edge if the group is. Panics if a vertex is in more than one group.
+pub fn contract_vertices<G>(original_graph: G, groups: Vec<Vec<usize>>) -> (UnGraph, IndexMapping)
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let (contracted_graph, _, index_mapping) = contract_vertex_groups(original_graph, &groups);
+    (contracted_graph, index_mapping)
+}
+
+/// Same as [`contract_vertices`], but also returns the contracted vertex of each group, or `None`
+/// if no edge of the graph is incident to the group.
+fn contract_vertex_groups<G>(
+    original_graph: G,
+    groups: &[Vec<usize>],
+) -> (UnGraph, Vec<Option<usize>>, IndexMapping)
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    // every vertex of a group is replaced by the first vertex of the group
+    let mut group_of = HashMap::<usize, usize>::new();
+    for (group_index, group) in groups.iter().enumerate() {
+        for &vertex in group {
+            let previous = group_of.insert(vertex, group_index);
+            if previous.is_some_and(|previous| previous != group_index) {
+                panic!("Vertex {} is in more than one group", vertex);
+            }
+        }
+    }
+    let transform = |vertex: usize| {
+        group_of
+            .get(&vertex)
+            .map_or(vertex, |&group_index| groups[group_index][0])
+    };
 
     let mut new_edges: Vec<(usize, usize)> = vec![];
 
@@ -301,16 +496,11 @@ where
     for edge in original_graph.edge_references() {
         let original_edge_index = EdgeIndexable::to_index(&original_graph, edge.id());
 
-        let mut edge_source = NodeIndexable::to_index(&original_graph, edge.source());
-        let mut edge_target = NodeIndexable::to_index(&original_graph, edge.target());
-
-        let s_before_transform = edge_source;
-        let t_before_transform = edge_target;
+        let s_before_transform = NodeIndexable::to_index(&original_graph, edge.source());
+        let t_before_transform = NodeIndexable::to_index(&original_graph, edge.target());
 
-        transform_if_in_set(&mut edge_source, &source_set, new_source);
-        transform_if_in_set(&mut edge_target, &source_set, new_source);
-        transform_if_in_set(&mut edge_source, &destination_set, new_destination);
-        transform_if_in_set(&mut edge_target, &destination_set, new_destination);
+        let edge_source = transform(s_before_transform);
+        let edge_target = transform(t_before_transform);
 
         // add source and target indices to the index mapping in order
         let smaller = min(edge_source, edge_target);
@@ -330,7 +520,7 @@ where
                 edge_vertex_index_mapping.add_vertex(s, s_before_transform);
                 edge_vertex_index_mapping.add_vertex(t, t_before_transform);
 
-                // add edge to new graph if both endpoints are not in the source/target
+                // add edge to new graph if both endpoints are not in the same group
                 // note that we use the unmapped transformed indices for this
                 if edge_source != edge_target {
                     // check if edge has already been added using position
@@ -352,19 +542,86 @@ where
         };
     }
 
-    // after we've added all edges, we can return the new contracted graph
-    match (
-        creation_index_mapping.get(&new_source),
-        creation_index_mapping.get(&new_destination),
-    ) {
-        (Some(&s), Some(&t)) => (
-            UnGraph::from_edges(new_edges),
-            s,
-            t,
-            edge_vertex_index_mapping,
-        ),
-        (_, _) => panic!("New edge source and target should always be in the index mapping"),
+    // after we've added all edges, we can return the new contracted graph, which has a vertex for
+    // every index in the mapping even if all of its edges were contracted away
+    let mut contracted_graph =
+        UnGraph::with_capacity(creation_index_mapping.len(), new_edges.len());
+    for _ in 0..creation_index_mapping.len() {
+        contracted_graph.add_node(());
+    }
+    for (s, t) in new_edges {
+        contracted_graph.add_edge(NodeIndex::new(s), NodeIndex::new(t), ());
     }
+
+    let group_vertices = groups
+        .iter()
+        .map(|group| {
+            group
+                .first()
+                .and_then(|representative| creation_index_mapping.get(representative).copied())
+        })
+        .collect::<Vec<_>>();
+
+    // members of a group without edges of their own are still part of its contracted vertex, so
+    // that e.g. an isolated destination vertex ends up on the destination side of a cut
+    for (group, &contracted) in groups.iter().zip(&group_vertices) {
+        if let Some(contracted) = contracted {
+            for &vertex in group {
+                edge_vertex_index_mapping.add_vertex(contracted, vertex);
+            }
+        }
+    }
+    (contracted_graph, group_vertices, edge_vertex_index_mapping)
+}
+
+/// Maps paths of a graph created by [`create_contracted_graph`] back to the graph that was
+/// contracted, where only the edges marked in `edges_in_use` are present. A contracted edge that
+/// stands for parallel edges is mapped to a different one of them for each path that uses it.
+pub(crate) fn map_paths_to_original(
+    graph: &UnGraph,
+    paths: &[Path],
+    index_mapping: &IndexMapping,
+    edges_in_use: &[bool],
+) -> Vec<Path> {
+    let mut taken = vec![false; edges_in_use.len()];
+    paths
+        .iter()
+        .map(|path| {
+            let first_vertices = index_mapping
+                .vertex_contracted_to_original
+                .get(&path.source())
+                .expect("The index mapping should contain the vertices of the path");
+            let mut vertices = vec![];
+            let mut edges = vec![];
+            for contracted_edge in &path.edges {
+                let edge = index_mapping
+                    .edge_contracted_to_original
+                    .get(contracted_edge)
+                    .expect("The index mapping should contain the edges of the path")
+                    .iter()
+                    .copied()
+                    .find(|&edge| edges_in_use[edge] && !taken[edge])
+                    .expect("A contracted edge should have an edge in use for each path using it");
+                taken[edge] = true;
+                let (a, b) = graph
+                    .edge_endpoints(EdgeIndex::new(edge))
+                    .map(|(a, b)| (a.index(), b.index()))
+                    .expect("The edge should belong to the graph");
+                let from = match vertices.last() {
+                    Some(&vertex) => vertex,
+                    None => {
+                        let vertex = if first_vertices.contains(&a) { a } else { b };
+                        vertices.push(vertex);
+                        vertex
+                    }
+                };
+                debug_assert!(from == a || from == b, "Illegal endpoint {}", from);
+                vertices.push(if from == a { b } else { a });
+                edges.push(edge);
+            }
+            Path { vertices, edges }
+        })
+        .collect()
 }
 
 pub fn get_augmenting_paths_and_residual_graph_for_sets<G>(
@@ -372,7 +629,7 @@ pub fn get_augmenting_paths_and_residual_graph_for_sets<G>(
     source_set: Vec<usize>,
     destination_set: Vec<usize>,
     k: usize,
-    edges_in_use: &Vec<bool>,
+    edges_in_use: &[bool],
 ) -> Option<(Vec<Path>, ResidualGraph, IndexMapping)>
 where
     G: NodeIndexable
@@ -388,10 +645,26 @@ where
         return None;
     }
 
-    fn get_new_graph_edge_capacities(
-        in_use: &Vec<bool>,
-        index_mapping: &IndexMapping,
-    ) -> Vec<usize> {
+    // neither can there be if the sets overlap, e.g. when branching added a destination vertex to
+    // the source set, or if one of the sets has no edges to contract
+    if source_set
+        .iter()
+        .any(|vertex| destination_set.contains(vertex))
+    {
+        return None;
+    }
+
+    let touches_set = |set: &[usize]| {
+        original_graph.edge_references().any(|edge| {
+            set.contains(&NodeIndexable::to_index(&original_graph, edge.source()))
+                || set.contains(&NodeIndexable::to_index(&original_graph, edge.target()))
+        })
+    };
+    if !touches_set(&source_set) || !touches_set(&destination_set) {
+        return None;
+    }
+
+    fn get_new_graph_edge_capacities(in_use: &[bool], index_mapping: &IndexMapping) -> Vec<usize> {
         let mut ret = vec![0; index_mapping.edge_contracted_to_original.len()];
         for (key, values) in index_mapping.edge_contracted_to_original.clone() {
             ret[key] = values.iter().filter(|&&value| in_use[value]).count();
@@ -400,20 +673,18 @@ where
     }
 
     let (graph, source, destination, index_mapping) =
-        create_contracted_graph(&original_graph, source_set, destination_set);
+        create_contracted_graph(original_graph, source_set, destination_set);
 
-    let new_graph_edge_capacities = get_new_graph_edge_capacities(&edges_in_use, &index_mapping);
+    let new_graph_edge_capacities = get_new_graph_edge_capacities(edges_in_use, &index_mapping);
 
-    match get_augmenting_paths_and_residual_graph(
+    get_augmenting_paths_and_residual_graph(
         &graph,
         NodeIndex::from(source),
         NodeIndex::from(destination),
         k,
         &new_graph_edge_capacities,
-    ) {
-        Some((paths, residual)) => Some((paths, residual, index_mapping)),
-        None => None,
-    }
+    )
+    .map(|(paths, residual)| (paths, residual, index_mapping))
 }
 
 #[cfg(test)]
@@ -421,12 +692,16 @@ mod tests {
     use std::collections::HashMap;
 
     use petgraph::graph::{EdgeReference, NodeIndex, UnGraph};
-    use petgraph::visit::{EdgeRef, NodeIndexable};
+    use petgraph::visit::{Bfs, EdgeRef, NodeIndexable};
 
+    use crate::cuts::flow::{has_augmenting_path, other_endpoint};
+    use crate::cuts::minimum_cut::minimum_cut;
     use crate::cuts::path_residual::{
-        create_contracted_graph, get_augmenting_paths_and_residual_graph,
-        get_augmenting_paths_and_residual_graph_for_sets, has_augmenting_path, other_endpoint,
+        augmenting_paths, contract_vertices, create_contracted_graph,
+        get_augmenting_paths_and_residual_graph, get_augmenting_paths_and_residual_graph_for_sets,
+        residual_graph, try_augmenting_paths, IndexMapping, Path,
     };
+    use crate::Error;
 
     fn get_path_vertex_tuples(
         graph: &UnGraph<(), ()>,
@@ -448,15 +723,21 @@ mod tests {
 
     #[test]
     fn simple_augmenting_path() {
-        let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4)]);
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 4);
         let mut path = vec![None; graph.node_count()];
-        let mut edge_capacities = vec![1; graph.edge_count()];
+        let edge_capacities = vec![1; graph.edge_count()];
 
         // check that we find a path
-        let found_path =
-            has_augmenting_path(&graph, source, destination, &mut path, &mut edge_capacities);
+        let found_path = has_augmenting_path(
+            &graph,
+            source,
+            destination,
+            &mut path,
+            &edge_capacities,
+            &vec![0; graph.edge_count()],
+        );
         assert!(found_path);
 
         // check the correctness of the path
@@ -467,15 +748,20 @@ mod tests {
 
     #[test]
     fn simple_augmenting_path_with_alternatives() {
-        let graph =
-            UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 5), (0, 3), (3, 4), (4, 5)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 5), (0, 3), (3, 4), (4, 5)]);
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 5);
         let mut path = vec![None; graph.node_count()];
-        let mut edge_capacities = vec![1; graph.edge_count()];
+        let edge_capacities = vec![1; graph.edge_count()];
 
-        let found_path =
-            has_augmenting_path(&graph, source, destination, &mut path, &mut edge_capacities);
+        let found_path = has_augmenting_path(
+            &graph,
+            source,
+            destination,
+            &mut path,
+            &edge_capacities,
+            &vec![0; graph.edge_count()],
+        );
         assert!(found_path);
 
         let path_vertex_tuples = get_path_vertex_tuples(&graph, &path, destination);
@@ -486,33 +772,45 @@ mod tests {
 
     #[test]
     fn no_augmenting_path() {
-        let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (2, 3)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (2, 3)]);
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 3);
         let mut path = vec![None; graph.node_count()];
-        let mut edge_capacities = vec![1; graph.edge_count()];
+        let edge_capacities = vec![1; graph.edge_count()];
 
-        let found_path =
-            has_augmenting_path(&graph, source, destination, &mut path, &mut edge_capacities);
+        let found_path = has_augmenting_path(
+            &graph,
+            source,
+            destination,
+            &mut path,
+            &edge_capacities,
+            &vec![0; graph.edge_count()],
+        );
         assert!(!found_path);
     }
 
     #[test]
     fn no_augmenting_path_available() {
-        let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 3);
         let mut path = vec![None; graph.node_count()];
-        let mut edge_capacities = vec![1, 0, 1];
+        let edge_capacities = vec![1, 0, 1];
 
-        let found_path =
-            has_augmenting_path(&graph, source, destination, &mut path, &mut edge_capacities);
+        let found_path = has_augmenting_path(
+            &graph,
+            source,
+            destination,
+            &mut path,
+            &edge_capacities,
+            &vec![0; graph.edge_count()],
+        );
         assert!(!found_path);
     }
 
     #[test]
     fn only_one_available_augmenting_path() {
-        let graph = UnGraph::<(), ()>::from_edges(&[
+        let graph = UnGraph::<(), ()>::from_edges([
             (0, 1),
             (1, 2),
             (2, 7),
@@ -530,8 +828,14 @@ mod tests {
         edge_capacities[2] = 0;
         edge_capacities[4] = 0;
 
-        let found_path =
-            has_augmenting_path(&graph, source, destination, &mut path, &mut edge_capacities);
+        let found_path = has_augmenting_path(
+            &graph,
+            source,
+            destination,
+            &mut path,
+            &edge_capacities,
+            &vec![0; graph.edge_count()],
+        );
         assert!(found_path);
 
         let path_vertex_tuples = get_path_vertex_tuples(&graph, &path, destination);
@@ -541,25 +845,25 @@ mod tests {
 
     #[test]
     fn no_augmenting_path_if_no_edges_have_enough_capacity() {
-        let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 3), (0, 2), (2, 3)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 3), (0, 2), (2, 3)]);
 
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 3);
-        let mut edge_capacities = vec![2, 0, 0, 1];
+        let edge_capacities = vec![2, 0, 0, 1];
 
         let res = get_augmenting_paths_and_residual_graph(
             &graph,
             source,
             destination,
             2,
-            &mut edge_capacities,
+            &edge_capacities,
         );
         assert!(res.is_none());
     }
 
     #[test]
     fn get_all_augmenting_paths() {
-        let graph = UnGraph::<(), ()>::from_edges(&[
+        let graph = UnGraph::<(), ()>::from_edges([
             (0, 1),
             (1, 2),
             (2, 6),
@@ -579,19 +883,43 @@ mod tests {
             3,
             &mut vec![1; graph.edge_count()],
         ) {
-            let expected_paths = vec![vec![0, 1, 2, 6], vec![0, 3, 6], vec![0, 4, 5, 6]];
+            let expected_paths = [vec![0, 1, 2, 6], vec![0, 3, 6], vec![0, 4, 5, 6]];
             assert!(paths
                 .iter()
                 .all(|path| { expected_paths.contains(&path.vertices) }));
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
     #[test]
-    fn no_augmenting_paths_for_too_small_k() {
+    fn augmenting_paths_can_cancel_flow() {
+        // the shortest path 0-1-2-5 blocks both other paths unless its flow on (1, 2) is
+        // cancelled by the path 0-3-2-1-4-5
         let graph =
-            UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 4), (0, 2), (2, 4), (0, 3), (3, 4)]);
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 5), (0, 3), (3, 2), (1, 4), (4, 5)]);
+        let source = NodeIndexable::from_index(&graph, 0);
+        let destination = NodeIndexable::from_index(&graph, 5);
+
+        let (paths, _) = get_augmenting_paths_and_residual_graph(
+            &graph,
+            source,
+            destination,
+            2,
+            &vec![1; graph.edge_count()],
+        )
+        .unwrap();
+        let mut path_vertices = paths
+            .into_iter()
+            .map(|path| path.vertices)
+            .collect::<Vec<_>>();
+        path_vertices.sort();
+        assert_eq!(vec![vec![0, 1, 4, 5], vec![0, 3, 2, 5]], path_vertices);
+    }
+
+    #[test]
+    fn no_augmenting_paths_for_too_small_k() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 4), (0, 2), (2, 4), (0, 3), (3, 4)]);
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 4);
         let k = 2;
@@ -608,7 +936,7 @@ mod tests {
 
     #[test]
     fn correct_residual_graph() {
-        let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 3)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (0, 3)]);
         let source = NodeIndexable::from_index(&graph, 0);
         let destination = NodeIndexable::from_index(&graph, 2);
 
@@ -619,7 +947,7 @@ mod tests {
             1,
             &mut vec![1; graph.edge_count()],
         ) {
-            let residual_reverse_expected_edges = vec![(1, 2), (0, 1), (0, 3), (3, 0)];
+            let residual_reverse_expected_edges = [(1, 2), (0, 1), (0, 3), (3, 0)];
 
             assert_eq!(4usize, residual_reverse.edge_count());
             assert!(residual_reverse.edge_references().all(|edge| {
@@ -627,14 +955,101 @@ mod tests {
                     .contains(&(edge.source().index(), edge.target().index()))
             }));
         } else {
-            assert!(false);
+            panic!();
         }
     }
 
+    #[test]
+    fn antiparallel_edges_give_each_residual_arc_once() {
+        // the edges between 0 and 1 point in opposite directions, as in a converted directed graph
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 0), (1, 2)]);
+        let source = NodeIndexable::from_index(&graph, 0);
+        let destination = NodeIndexable::from_index(&graph, 2);
+
+        let (paths, residual_reverse) =
+            get_augmenting_paths_and_residual_graph(&graph, source, destination, 2, &[1; 3])
+                .unwrap();
+        assert_eq!(1, paths.len());
+
+        let mut arcs = residual_reverse
+            .edge_references()
+            .map(|edge| (edge.source().index(), edge.target().index()))
+            .collect::<Vec<_>>();
+        arcs.sort();
+        assert_eq!(vec![(0, 1), (1, 0), (1, 2)], arcs);
+    }
+
+    #[test]
+    fn paths_through_parallel_edges_saturate_both() {
+        // both paths go from 0 over 1 to 2, each through its own pair of parallel edges
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (1, 2), (1, 2)]);
+        let source = NodeIndexable::from_index(&graph, 0);
+        let destination = NodeIndexable::from_index(&graph, 2);
+
+        let (paths, residual_reverse) =
+            get_augmenting_paths_and_residual_graph(&graph, source, destination, 2, &[1; 4])
+                .unwrap();
+        let mut edges = paths
+            .iter()
+            .flat_map(|path| path.edges.clone())
+            .collect::<Vec<_>>();
+        edges.sort();
+        assert_eq!(vec![0, 1, 2, 3], edges);
+
+        // every edge carries flow, so there is no residual arc back towards the source
+        let mut arcs = residual_reverse
+            .edge_references()
+            .map(|edge| (edge.source().index(), edge.target().index()))
+            .collect::<Vec<_>>();
+        arcs.sort();
+        assert_eq!(vec![(0, 1), (1, 2)], arcs);
+    }
+
+    #[test]
+    fn destination_closest_cut_from_residual_graph() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (3, 5)]);
+        let source_set = vec![0];
+        let destination_set = vec![4, 5];
+
+        let (residual, index_mapping) =
+            residual_graph(&graph, source_set.clone(), destination_set.clone(), 2).unwrap();
+
+        // the destination side consists of everything reachable from the contracted destination
+        let contracted_destination = index_mapping
+            .vertex_contracted_to_original
+            .iter()
+            .find(|(_, originals)| originals.contains(&4))
+            .map(|(&contracted, _)| contracted)
+            .unwrap();
+        let mut destination_side = vec![];
+        let mut bfs = Bfs::new(&residual, NodeIndex::new(contracted_destination));
+        while let Some(vertex) = bfs.next(&residual) {
+            destination_side.extend(&index_mapping.vertex_contracted_to_original[&vertex.index()]);
+        }
+        destination_side.sort();
+
+        let cut_edges = graph
+            .edge_references()
+            .filter(|edge| {
+                destination_side.contains(&edge.source().index())
+                    != destination_side.contains(&edge.target().index())
+            })
+            .map(|edge| edge.id().index())
+            .collect::<Vec<_>>();
+
+        let min_cut = minimum_cut(&graph, source_set, destination_set).unwrap();
+        let mut min_cut_destination_side = min_cut.destination_set;
+        min_cut_destination_side.sort();
+        let mut min_cut_edges = min_cut.cut_edge_set;
+        min_cut_edges.sort();
+        assert_eq!(min_cut_destination_side, destination_side);
+        assert_eq!(min_cut_edges, cut_edges);
+        assert_eq!(vec![4, 5], cut_edges);
+    }
+
     #[test]
     fn correct_contracted_graph() {
-        let graph =
-            UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (1, 4), (2, 4), (3, 4)]);
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (1, 4), (2, 4), (3, 4)]);
         let source_set = vec![0, 1];
         let destination_set = vec![3, 4];
 
@@ -657,20 +1072,55 @@ mod tests {
         assert!(edge_indices.contains(&(1, 2)));
         assert_eq!(0, new_source);
         assert_eq!(2, new_dest);
+        for (&key, values) in &index_mapping.vertex_contracted_to_original {
+            for &value in values {
+                assert_eq!(Some(key), index_mapping.contracted_vertex_of(value));
+            }
+        }
+        for (&key, values) in &index_mapping.edge_contracted_to_original {
+            for &value in values {
+                assert_eq!(Some(key), index_mapping.contracted_edge_of(value));
+            }
+        }
+        assert_eq!(None, index_mapping.contracted_edge_of(0));
         for (key, values) in index_mapping.vertex_contracted_to_original {
             match expected_vertex_mapping.get(&key) {
-                None => assert!(false),
+                None => panic!(),
                 Some(expected_values) => assert_eq!(expected_values.clone(), values),
             }
         }
         for (key, values) in index_mapping.edge_contracted_to_original {
             match expected_edge_mapping.get(&key) {
-                None => assert!(false),
+                None => panic!(),
                 Some(expected_values) => assert_eq!(expected_values.clone(), values),
             }
         }
     }
 
+    #[test]
+    fn contract_three_groups() {
+        let graph =
+            UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (0, 5), (1, 4)]);
+
+        let (contracted_graph, index_mapping) =
+            contract_vertices(&graph, vec![vec![0, 1], vec![2, 3], vec![4, 5]]);
+        let edges = contracted_graph
+            .edge_references()
+            .map(|edge| (edge.source().index(), edge.target().index()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(3, contracted_graph.node_count());
+        assert_eq!(vec![(0, 1), (1, 2), (0, 2)], edges);
+        assert_eq!(
+            &HashMap::from([(0, vec![0, 1]), (1, vec![2, 3]), (2, vec![4, 5])]),
+            index_mapping.vertex_map()
+        );
+        assert_eq!(
+            &HashMap::from([(0, vec![1]), (1, vec![3]), (2, vec![5, 6])]),
+            index_mapping.edge_map()
+        );
+    }
+
     #[test]
     fn correct_augmented_paths_and_residual_for_sets() {
         /* Visualization of the graph used
@@ -682,7 +1132,7 @@ mod tests {
           \ |         /
            -3---5---8-
         */
-        let original_graph = UnGraph::<(), ()>::from_edges(&[
+        let original_graph = UnGraph::<(), ()>::from_edges([
             (0, 1),
             (0, 2),
             (0, 3),
@@ -711,7 +1161,7 @@ mod tests {
             &vec![true; original_graph.edge_count()],
         ) {
             Some((paths, residual, index_mapping)) => {
-                let expected_paths_edges = vec![vec![1, 3, 5], vec![0, 2, 4, 6]];
+                let expected_paths_edges = [vec![1, 3, 5], vec![0, 2, 4, 6]];
                 assert!(paths
                     .iter()
                     .all(|path| { expected_paths_edges.contains(&path.edges) }));
@@ -720,7 +1170,91 @@ mod tests {
                 assert_eq!(8, index_mapping.vertex_contracted_to_original.keys().len());
                 assert_eq!(8, index_mapping.edge_contracted_to_original.keys().len());
             }
-            None => assert!(false),
+            None => panic!(),
         }
     }
+
+    #[test]
+    fn path_source() {
+        let paths = [
+            Path::new(vec![3, 1, 4], vec![5, 0]),
+            Path::new(vec![3, 4], vec![1]),
+        ];
+
+        assert_eq!(3, Path::get_source(&paths));
+        assert_eq!(4, Path::get_destination(&paths));
+        assert_eq!(NodeIndex::new(3), Path::get_source_node_index(&paths));
+    }
+
+    #[test]
+    fn source_equal_to_destination() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+
+        assert_eq!(
+            Err(Error::SourceEqualsDestination(1)),
+            try_augmenting_paths(&graph, 1, 1, 2).map(|paths| paths.is_some())
+        );
+        assert!(get_augmenting_paths_and_residual_graph(
+            &graph,
+            NodeIndex::new(1),
+            NodeIndex::new(1),
+            2,
+            &[1, 1]
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn original_destinations_of_contracted_destination() {
+        // 0 - 1 - 2 and 1 - 3, where 2 and 3 are contracted into one destination
+        let original_graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let (graph, source, destination, index_mapping) =
+            create_contracted_graph(&original_graph, vec![0], vec![3, 2]);
+        let paths = augmenting_paths(&graph, source, destination, 2).unwrap();
+
+        assert_eq!(1, paths.len());
+        assert_eq!(destination, paths[0].destination());
+        assert_eq!(
+            vec![2, 3],
+            paths[0].get_original_destinations(&index_mapping)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "A path should have one vertex more than it has edges")]
+    #[cfg(debug_assertions)]
+    fn path_with_too_many_edges() {
+        Path::new(vec![0, 1], vec![0, 1]);
+    }
+
+    #[test]
+    fn path_accessors() {
+        // 3 - 1 - 4 - 2
+        let path = Path {
+            vertices: vec![3, 1, 4, 2],
+            edges: vec![5, 0, 2],
+        };
+
+        assert_eq!(3, path.hop_count());
+        assert_eq!(3, path.source());
+
+        let paths = [path];
+        assert_eq!(NodeIndex::new(3), Path::get_source_node_index(&paths));
+        assert_eq!(NodeIndex::new(2), Path::get_destination_node_index(&paths));
+    }
+
+    #[test]
+    fn index_mapping_accessors() {
+        let vertex_map = HashMap::from([(0, vec![0, 1]), (1, vec![2])]);
+        let edge_map = HashMap::from([(0, vec![1, 2])]);
+        let index_mapping = IndexMapping::from(vertex_map.clone(), edge_map.clone());
+
+        assert_eq!(&vertex_map, index_mapping.vertex_map());
+        assert_eq!(&edge_map, index_mapping.edge_map());
+        assert_eq!(Some(&[0, 1][..]), index_mapping.original_vertices(0));
+        assert_eq!(Some(&[2][..]), index_mapping.original_vertices(1));
+        assert_eq!(None, index_mapping.original_vertices(2));
+        assert_eq!(Some(&[1, 2][..]), index_mapping.original_edges(0));
+        assert_eq!(None, index_mapping.original_edges(1));
+    }
 }
@@ -0,0 +1,186 @@
+use std::f64::consts::PI;
+
+use petgraph::dot::{Config, Dot};
+use petgraph::graph::NodeIndex;
+use petgraph::visit::{
+    EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable,
+};
+
+use petgraph::{Graph, Undirected};
+
+use crate::cuts::cut::Cut;
+
+const ASCII_WIDTH: usize = 64;
+const ASCII_HEIGHT: usize = 24;
+const ASCII_EDGE: char = '.';
+const ASCII_CUT_EDGE: char = '#';
+const DOT_CUT_EDGE_ATTRIBUTES: &str = "style=bold,color=green";
+
+/// Draws the graph as ASCII art for a quick look in the terminal. The vertices are placed on an
+/// ellipse and labelled with their indices, the edges are drawn with `.` and the cut edges with
+/// `#`. This stays legible up to about 20 vertices.
+pub fn render_ascii<G>(graph: G, cut: &Cut) -> String
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences + IntoNodeIdentifiers,
+{
+    let vertices = graph
+        .node_identifiers()
+        .map(|vertex| NodeIndexable::to_index(&graph, vertex))
+        .collect::<Vec<_>>();
+
+    // cells are about twice as high as they are wide, so the ellipse looks like a circle
+    let (center_x, center_y) = ((ASCII_WIDTH / 2) as f64, (ASCII_HEIGHT / 2) as f64);
+    let (radius_x, radius_y) = (center_x - 4.0, center_y - 2.0);
+    let mut positions = vec![None; graph.node_bound()];
+    for (i, &vertex) in vertices.iter().enumerate() {
+        let angle = 2.0 * PI * i as f64 / vertices.len() as f64 - PI / 2.0;
+        positions[vertex] = Some((
+            (center_x + radius_x * angle.cos()).round() as isize,
+            (center_y + radius_y * angle.sin()).round() as isize,
+        ));
+    }
+
+    let mut canvas = vec![vec![' '; ASCII_WIDTH]; ASCII_HEIGHT];
+    let mut edges = graph
+        .edge_references()
+        .map(|edge| {
+            let in_cut = cut
+                .cut_edge_set
+                .contains(&EdgeIndexable::to_index(&graph, edge.id()));
+            let source = positions[NodeIndexable::to_index(&graph, edge.source())];
+            let target = positions[NodeIndexable::to_index(&graph, edge.target())];
+            (in_cut, source, target)
+        })
+        .collect::<Vec<_>>();
+    // the cut edges are drawn last so that other edges do not hide them
+    edges.sort_by_key(|&(in_cut, _, _)| in_cut);
+    for (in_cut, source, target) in edges {
+        if let (Some(source), Some(target)) = (source, target) {
+            let symbol = if in_cut { ASCII_CUT_EDGE } else { ASCII_EDGE };
+            draw_line(&mut canvas, source, target, symbol);
+        }
+    }
+
+    for &vertex in &vertices {
+        if let Some((x, y)) = positions[vertex] {
+            for (offset, digit) in vertex.to_string().chars().enumerate() {
+                set_cell(&mut canvas, x + offset as isize, y, digit);
+            }
+        }
+    }
+
+    canvas
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Writes the graph in the DOT format of Graphviz with [`Dot`], where the cut edges are drawn bold
+/// and green. The vertices are the vertex indices of the graph, including removed ones.
+pub fn cut_to_dot<G>(graph: G, cut: &Cut) -> String
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut dot_graph = Graph::<(), &'static str, Undirected, usize>::with_capacity(
+        graph.node_bound(),
+        graph.edge_bound(),
+    );
+    for _ in 0..graph.node_bound() {
+        dot_graph.add_node(());
+    }
+    for edge in graph.edge_references() {
+        let attributes = if cut
+            .cut_edge_set
+            .contains(&EdgeIndexable::to_index(&graph, edge.id()))
+        {
+            DOT_CUT_EDGE_ATTRIBUTES
+        } else {
+            ""
+        };
+        dot_graph.add_edge(
+            NodeIndex::new(NodeIndexable::to_index(&graph, edge.source())),
+            NodeIndex::new(NodeIndexable::to_index(&graph, edge.target())),
+            attributes,
+        );
+    }
+
+    format!(
+        "{:?}",
+        Dot::with_attr_getters(
+            &dot_graph,
+            &[Config::NodeNoLabel, Config::EdgeNoLabel],
+            &|_, edge| edge.weight().to_string(),
+            &|_, _| String::new(),
+        )
+    )
+}
+
+fn set_cell(canvas: &mut [Vec<char>], x: isize, y: isize, symbol: char) {
+    if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+        if let Some(cell) = canvas.get_mut(y).and_then(|row| row.get_mut(x)) {
+            *cell = symbol;
+        }
+    }
+}
+
+/// Draws a line between the two cells with Bresenham's algorithm.
+fn draw_line(canvas: &mut [Vec<char>], from: (isize, isize), to: (isize, isize), symbol: char) {
+    let (mut x, mut y) = from;
+    let (dx, dy) = ((to.0 - x).abs(), -(to.1 - y).abs());
+    let (step_x, step_y) = ((to.0 - x).signum(), (to.1 - y).signum());
+    let mut error = dx + dy;
+    loop {
+        set_cell(canvas, x, y, symbol);
+        if (x, y) == to {
+            break;
+        }
+        let doubled_error = 2 * error;
+        if doubled_error >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled_error <= dx {
+            error += dx;
+            y += step_y;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use crate::cuts::render::{cut_to_dot, render_ascii};
+    use crate::cuts::Cut;
+
+    #[test]
+    fn ascii_has_a_label_per_vertex() {
+        // a cycle on 0..8, cut into two paths by the edges (1, 2) and (5, 6)
+        let graph = UnGraph::<(), ()>::from_edges((0..8).map(|i| (i, (i + 1) % 8)));
+        let cut = Cut::new(vec![2, 3, 4, 5], vec![6, 7, 0, 1], vec![1, 5]);
+
+        let ascii = render_ascii(&graph, &cut);
+        for vertex in 0..8 {
+            let label = char::from_digit(vertex, 10).unwrap();
+            assert_eq!(1, ascii.chars().filter(|&c| c == label).count());
+        }
+        assert!(ascii.contains('#'));
+        assert!(ascii.contains('.'));
+        assert!(ascii.lines().all(|line| line.len() <= 64));
+    }
+
+    #[test]
+    fn dot_highlights_cut_edges() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1]);
+
+        let dot = cut_to_dot(&graph, &cut);
+        let highlighted = dot
+            .lines()
+            .filter(|line| line.contains("style=bold,color=green"))
+            .collect::<Vec<_>>();
+        assert_eq!(1, highlighted.len());
+        assert!(highlighted[0].trim_start().starts_with("1 -- 2"));
+    }
+}
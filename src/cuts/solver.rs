@@ -0,0 +1,439 @@
+use std::collections::VecDeque;
+
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::cuts::cut::ImportantCut;
+use crate::cuts::flow::{flow_direction, residual_capacity};
+use crate::cuts::important_cut::{important_cuts_from_flow, sort_cuts};
+use crate::cuts::memo::{BranchMemo, MemoStats};
+use crate::cuts::path_residual::UnGraph;
+use crate::cuts::verify::validate_sets;
+use crate::Error;
+
+/// Keeps a maximum flow between a source set and a destination set up to date while edges are
+/// removed from and added to the graph, so that the flow does not have to be recomputed from
+/// scratch after every change.
+///
+/// Removing an edge only disables it, so the indices of the other edges stay the same, like for a
+/// `StableGraph`. Added edges get the next free edge index.
+///
+/// [`CutSolver::important_cuts`] returns early if the cached flow is larger than k. Otherwise the
+/// search starts from the cached flow, so the minimum cut of its first branch needs no further
+/// augmentation. The later branches change the source set and compute their own flows. The
+/// minimum cut of every branch is kept in a memo, so that a repeated search, e.g. for another k or
+/// after an edge has been removed, only computes the flows of the branches it has not seen before.
+#[derive(Debug)]
+pub struct CutSolver {
+    graph: UnGraph,
+    source_set: Vec<usize>,
+    destination_set: Vec<usize>,
+    edges_in_use: Vec<bool>,
+    edge_flows: Vec<isize>,
+    flow_value: usize,
+    memo: BranchMemo,
+}
+
+impl CutSolver {
+    /// Computes the initial maximum flow. Panics if one of the sets is empty, the sets overlap or
+    /// a vertex of the sets is not in the graph, see [`CutSolver::try_new`].
+    pub fn new(graph: UnGraph, source_set: Vec<usize>, destination_set: Vec<usize>) -> Self {
+        Self::try_new(graph, source_set, destination_set)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Same as [`CutSolver::new`], but returns an error instead of panicking if one of the sets is
+    /// empty, the sets overlap or a vertex of the sets is not in the graph.
+    pub fn try_new(
+        graph: UnGraph,
+        source_set: Vec<usize>,
+        destination_set: Vec<usize>,
+    ) -> Result<Self, Error> {
+        validate_sets(&source_set, &destination_set)?;
+        if let Some(&vertex) = source_set
+            .iter()
+            .chain(&destination_set)
+            .find(|&&vertex| vertex >= graph.node_count())
+        {
+            return Err(Error::MissingVertex(vertex));
+        }
+        let edge_count = graph.edge_count();
+        let mut solver = Self {
+            graph,
+            source_set,
+            destination_set,
+            edges_in_use: vec![true; edge_count],
+            edge_flows: vec![0; edge_count],
+            flow_value: 0,
+            memo: BranchMemo::new(None),
+        };
+        solver.augment();
+        Ok(solver)
+    }
+
+    /// Limits the number of branches kept in the memo, which is unlimited by default.
+    pub fn with_memo_capacity(mut self, capacity: Option<usize>) -> Self {
+        self.memo = BranchMemo::new(capacity);
+        self
+    }
+
+    /// How often the searches so far found the minimum cut of a branch in the memo.
+    pub fn memo_stats(&self) -> MemoStats {
+        self.memo.stats()
+    }
+
+    /// Value of the maximum flow, i.e. the size of a minimum cut between the sets.
+    pub fn flow_value(&self) -> usize {
+        self.flow_value
+    }
+
+    /// Removes the edge, cancelling the unit of flow going through it before re-augmenting.
+    /// Removing an edge that was already removed does nothing. Panics if the edge is not in the
+    /// graph.
+    pub fn remove_edge(&mut self, edge: usize) {
+        assert!(
+            edge < self.edges_in_use.len(),
+            "Edge {} should be in the graph",
+            edge
+        );
+        if !self.edges_in_use[edge] {
+            return;
+        }
+        if self.edge_flows[edge] != 0 {
+            self.cancel_flow(edge);
+        }
+        self.edges_in_use[edge] = false;
+        self.augment();
+    }
+
+    /// Adds an edge between two existing vertices and returns its index. The flow can grow by at
+    /// most one, so a single augmentation is enough. Panics if one of the vertices is not in the
+    /// graph.
+    pub fn add_edge(&mut self, source: usize, destination: usize) -> usize {
+        assert!(
+            source < self.graph.node_count() && destination < self.graph.node_count(),
+            "The endpoints of an added edge should be in the graph"
+        );
+        let edge = self
+            .graph
+            .add_edge(NodeIndex::new(source), NodeIndex::new(destination), ())
+            .index();
+        self.edges_in_use.push(true);
+        self.edge_flows.push(0);
+        // the edges in use of the stored branches no longer match the graph
+        self.memo.clear();
+        self.augment();
+        edge
+    }
+
+    /// Enumerates the important cuts of size at most k of the current graph, in the same order as
+    /// [`important_cuts`](crate::cuts::important_cuts).
+    pub fn important_cuts(&mut self, k: usize) -> Vec<ImportantCut> {
+        // the cached flow tells us right away if there are no cuts to find
+        if self.flow_value > k {
+            return vec![];
+        }
+
+        let cuts = important_cuts_from_flow(
+            &self.graph,
+            self.source_set.clone(),
+            self.destination_set.clone(),
+            k,
+            self.edges_in_use.clone(),
+            (self.flow_value, self.edge_flows.clone()),
+            &mut self.memo,
+        );
+        sort_cuts(cuts)
+    }
+
+    /// Edges incident to the vertex as (edge, other endpoint).
+    fn incident_edges(&self, vertex: usize) -> Vec<(usize, usize)> {
+        self.graph
+            .edges(NodeIndex::new(vertex))
+            .filter(|edge| self.edges_in_use[edge.id().index()])
+            .map(|edge| {
+                let other = if edge.source().index() == vertex {
+                    edge.target()
+                } else {
+                    edge.source()
+                };
+                (edge.id().index(), other.index())
+            })
+            .collect()
+    }
+
+    /// Whether the edge carries flow from `from` to `to`.
+    fn carries_flow(&self, edge: usize, from: usize, to: usize) -> bool {
+        flow_direction(from, to) * self.edge_flows[edge] > 0
+    }
+
+    /// Pushes flow along augmenting paths from the source set to the destination set until there
+    /// are none left.
+    fn augment(&mut self) {
+        loop {
+            let mut previous = vec![None; self.graph.node_count()];
+            let mut visited = vec![false; self.graph.node_count()];
+            let mut queue = VecDeque::new();
+            for &source in &self.source_set {
+                visited[source] = true;
+                queue.push_back(source);
+            }
+
+            let mut reached_destination = None;
+            'search: while let Some(vertex) = queue.pop_front() {
+                for (edge, next) in self.incident_edges(vertex) {
+                    let capacity = usize::from(self.edges_in_use[edge]);
+                    if visited[next]
+                        || residual_capacity(capacity, self.edge_flows[edge], vertex, next) <= 0
+                    {
+                        continue;
+                    }
+                    previous[next] = Some((edge, vertex));
+                    if self.destination_set.contains(&next) {
+                        reached_destination = Some(next);
+                        break 'search;
+                    }
+                    visited[next] = true;
+                    queue.push_back(next);
+                }
+            }
+
+            let Some(mut vertex) = reached_destination else {
+                return;
+            };
+            while let Some((edge, previous_vertex)) = previous[vertex] {
+                self.edge_flows[edge] += flow_direction(previous_vertex, vertex);
+                vertex = previous_vertex;
+            }
+            self.flow_value += 1;
+        }
+    }
+
+    /// Cancels the unit of flow going through the edge. We remove the flow from the edge and then
+    /// follow the flow backwards to the source set and forwards to the destination set, cancelling
+    /// it along the way. If the backward walk arrives back at the edge, the flow was part of a
+    /// cycle and the flow value stays the same.
+    fn cancel_flow(&mut self, edge: usize) {
+        let (a, b) = self
+            .graph
+            .edge_endpoints(EdgeIndex::new(edge))
+            .map(|(a, b)| (a.index(), b.index()))
+            .expect("Edge should exist");
+        let (from, to) = if self.carries_flow(edge, a, b) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        self.edge_flows[edge] -= flow_direction(from, to);
+
+        let mut vertex = from;
+        while !self.source_set.contains(&vertex) {
+            let (incoming, previous) = self
+                .incident_edges(vertex)
+                .into_iter()
+                .find(|&(incoming, previous)| self.carries_flow(incoming, previous, vertex))
+                .expect("Flow should be conserved at every vertex");
+            self.edge_flows[incoming] -= flow_direction(previous, vertex);
+            if previous == to {
+                return;
+            }
+            vertex = previous;
+        }
+
+        let mut vertex = to;
+        while !self.destination_set.contains(&vertex) {
+            let (outgoing, next) = self
+                .incident_edges(vertex)
+                .into_iter()
+                .find(|&(outgoing, next)| self.carries_flow(outgoing, vertex, next))
+                .expect("Flow should be conserved at every vertex");
+            self.edge_flows[outgoing] -= flow_direction(vertex, next);
+            vertex = next;
+        }
+        self.flow_value -= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::EdgeIndex;
+    use petgraph::stable_graph::StableUnGraph;
+
+    use crate::cuts::cut::ImportantCut;
+    use crate::cuts::important_cut::important_cuts;
+    use crate::cuts::minimum_cut::max_flow_between_sets;
+    use crate::cuts::path_residual::UnGraph;
+    use crate::cuts::solver::CutSolver;
+    use crate::Error;
+
+    fn sorted_cuts(cuts: Vec<ImportantCut>) -> Vec<Vec<usize>> {
+        // the edges of each cut are already sorted by ImportantCut::from
+        let mut cuts = ImportantCut::vec_edge_indices(cuts);
+        cuts.sort();
+        cuts.dedup();
+        cuts
+    }
+
+    #[test]
+    fn removing_cut_edge_matches_fresh_computation() {
+        //         2
+        //       /   \
+        // 0 - 1       4
+        //       \   /
+        //         3
+        let edges = [(0, 1), (1, 2), (1, 3), (2, 4), (3, 4)];
+        let mut solver = CutSolver::new(UnGraph::from_edges(edges), vec![0], vec![4]);
+        assert_eq!(1, solver.flow_value());
+        assert_eq!(
+            vec![vec![0], vec![3, 4]],
+            sorted_cuts(solver.important_cuts(2))
+        );
+
+        solver.remove_edge(3);
+        let mut graph = StableUnGraph::<(), (), usize>::from_edges(edges);
+        graph.remove_edge(EdgeIndex::new(3));
+
+        assert_eq!(
+            max_flow_between_sets(&graph, vec![0], vec![4]),
+            solver.flow_value()
+        );
+        assert_eq!(
+            sorted_cuts(important_cuts(&graph, vec![0], vec![4], 2)),
+            sorted_cuts(solver.important_cuts(2))
+        );
+    }
+
+    #[test]
+    fn flow_follows_added_and_removed_edges() {
+        // 0 - 1 - 2 with 0 and 2 also connected through 3
+        let mut solver = CutSolver::new(
+            UnGraph::from_edges([(0, 1), (1, 2), (0, 3), (3, 2)]),
+            vec![0],
+            vec![2],
+        );
+        assert_eq!(2, solver.flow_value());
+
+        let edge = solver.add_edge(0, 2);
+        assert_eq!(4, edge);
+        assert_eq!(3, solver.flow_value());
+
+        solver.remove_edge(1);
+        assert_eq!(2, solver.flow_value());
+        solver.remove_edge(1);
+        assert_eq!(2, solver.flow_value());
+        solver.remove_edge(3);
+        assert_eq!(1, solver.flow_value());
+        assert_eq!(vec![vec![4]], sorted_cuts(solver.important_cuts(1)));
+    }
+
+    #[test]
+    fn cached_flow_seeds_the_search_after_changes() {
+        // a 3 x 3 grid, whose corners 0 and 8 are the sets
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (3, 4),
+            (4, 5),
+            (6, 7),
+            (7, 8),
+            (0, 3),
+            (3, 6),
+            (1, 4),
+            (4, 7),
+            (2, 5),
+            (5, 8),
+        ];
+        let mut solver = CutSolver::new(UnGraph::from_edges(edges), vec![0], vec![8]);
+        solver.remove_edge(8);
+        solver.remove_edge(6);
+        let edge = solver.add_edge(2, 4);
+
+        // the edge is added first, as a stable graph would reuse the index of a removed edge
+        let mut graph = StableUnGraph::<(), (), usize>::from_edges(edges);
+        let added = graph.add_edge(2.into(), 4.into(), ());
+        graph.remove_edge(EdgeIndex::new(8));
+        graph.remove_edge(EdgeIndex::new(6));
+        assert_eq!(added.index(), edge);
+        for k in 0..=4 {
+            assert_eq!(
+                sorted_cuts(important_cuts(&graph, vec![0], vec![8], k)),
+                sorted_cuts(solver.important_cuts(k))
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_searches_reuse_the_memo() {
+        // a 3 x 3 grid, whose corners 0 and 8 are the sets
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (3, 4),
+            (4, 5),
+            (6, 7),
+            (7, 8),
+            (0, 3),
+            (3, 6),
+            (1, 4),
+            (4, 7),
+            (2, 5),
+            (5, 8),
+        ];
+        let graph = StableUnGraph::<(), (), usize>::from_edges(edges);
+        let mut solver = CutSolver::new(UnGraph::from_edges(edges), vec![0], vec![8]);
+        let mut memo_free = CutSolver::new(UnGraph::from_edges(edges), vec![0], vec![8])
+            .with_memo_capacity(Some(0));
+
+        // a single search never explores the same branch twice
+        let first = solver.important_cuts(3);
+        assert_eq!(0, solver.memo_stats().hits);
+        let entries = solver.memo_stats().entries;
+        assert!(entries > 0);
+
+        for k in [3, 2, 4] {
+            let expected = sorted_cuts(important_cuts(&graph, vec![0], vec![8], k));
+            assert_eq!(expected, sorted_cuts(solver.important_cuts(k)));
+            assert_eq!(expected, sorted_cuts(memo_free.important_cuts(k)));
+        }
+        assert_eq!(sorted_cuts(first), sorted_cuts(solver.important_cuts(3)));
+        // every search starts in the branch of the whole graph
+        assert!(solver.memo_stats().hits >= 4);
+        assert_eq!(0, memo_free.memo_stats().hits);
+        assert_eq!(0, memo_free.memo_stats().entries);
+
+        let edge = solver.add_edge(0, 8);
+        assert_eq!(0, solver.memo_stats().entries);
+        solver.remove_edge(edge);
+        assert_eq!(
+            sorted_cuts(important_cuts(&graph, vec![0], vec![8], 3)),
+            sorted_cuts(solver.important_cuts(3))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Vertex 5 is not in the graph")]
+    fn sets_outside_the_graph_are_rejected() {
+        CutSolver::new(UnGraph::from_edges([(0, 1)]), vec![0], vec![5]);
+    }
+
+    #[test]
+    fn try_new_rejects_invalid_sets() {
+        let graph = UnGraph::from_edges([(0, 1)]);
+
+        assert_eq!(
+            Some(Error::MissingVertex(5)),
+            CutSolver::try_new(graph.clone(), vec![0], vec![5]).err()
+        );
+        assert_eq!(
+            Some(Error::OverlappingSets(1)),
+            CutSolver::try_new(graph, vec![1], vec![1]).err()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Edge 3 should be in the graph")]
+    fn removing_missing_edge_panics() {
+        let mut solver = CutSolver::new(UnGraph::from_edges([(0, 1), (1, 2)]), vec![0], vec![2]);
+        solver.remove_edge(3);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use petgraph::graph::EdgeIndex;
+use petgraph::visit::{EdgeIndexable, EdgeRef, IntoEdgeReferences, NodeIndexable};
+
+use crate::cuts::compaction::Compaction;
+use crate::cuts::cut::Cut;
+use crate::cuts::minimum_cut::max_flow_value;
+use crate::cuts::path_residual::UnGraph;
+use crate::Error;
+
+/// Finds the vertices reachable from the start vertices using only the edges for which
+/// `edge_available` returns true. Edges are treated as undirected.
+pub(crate) fn reachable_vertices<G>(
+    graph: G,
+    start: &[usize],
+    edge_available: impl Fn(usize) -> bool,
+) -> HashSet<usize>
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let mut neighbors = HashMap::<usize, Vec<usize>>::new();
+    for edge in graph.edge_references() {
+        if !edge_available(EdgeIndexable::to_index(&graph, edge.id())) {
+            continue;
+        }
+        let source = NodeIndexable::to_index(&graph, edge.source());
+        let target = NodeIndexable::to_index(&graph, edge.target());
+        neighbors.entry(source).or_default().push(target);
+        neighbors.entry(target).or_default().push(source);
+    }
+
+    let mut visited = HashSet::<usize>::from_iter(start.iter().copied());
+    let mut queue = VecDeque::from_iter(start.iter().copied());
+    while let Some(vertex) = queue.pop_front() {
+        for &next in neighbors.get(&vertex).into_iter().flatten() {
+            if visited.insert(next) {
+                queue.push_back(next);
+            }
+        }
+    }
+    visited
+}
+
+/// Checks that the source and destination sets are nonempty and disjoint, which the algorithms
+/// rely on.
+pub(crate) fn validate_sets(source_set: &[usize], destination_set: &[usize]) -> Result<(), Error> {
+    if source_set.is_empty() || destination_set.is_empty() {
+        return Err(Error::EmptySet);
+    }
+    match destination_set
+        .iter()
+        .find(|vertex| source_set.contains(vertex))
+    {
+        Some(&vertex) => Err(Error::OverlappingSets(vertex)),
+        None => Ok(()),
+    }
+}
+
+/// Checks that the cut separates the source set from the destination set, i.e. that no
+/// destination vertex can be reached from a source vertex once the cut edges are removed. In
+/// addition, every cut edge has to exist and cross the partition of the cut, having one endpoint
+/// on its source side and the other on its destination side.
+pub fn verify_cut<G>(graph: G, cut: &Cut, source_set: &[usize], destination_set: &[usize]) -> bool
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let crossing_edges = graph
+        .edge_references()
+        .filter(|edge| {
+            cut.cut_edge_set
+                .contains(&EdgeIndexable::to_index(&graph, edge.id()))
+        })
+        .filter(|edge| {
+            let source = NodeIndexable::to_index(&graph, edge.source());
+            let target = NodeIndexable::to_index(&graph, edge.target());
+            (cut.source_set.contains(&source) && cut.destination_set.contains(&target))
+                || (cut.source_set.contains(&target) && cut.destination_set.contains(&source))
+        })
+        .count();
+    if crossing_edges != cut.cut_edge_set.iter().collect::<HashSet<_>>().len() {
+        return false;
+    }
+
+    let reachable = reachable_vertices(graph, source_set, |edge| !cut.cut_edge_set.contains(&edge));
+    destination_set
+        .iter()
+        .all(|vertex| !reachable.contains(vertex))
+}
+
+/// Checks that the given edges form an important cut between the source and destination sets.
+///
+/// The source side R of the cut is the set of vertices reachable from the source set once the
+/// edges are removed. The edges form an important cut if they separate the sets, are exactly the
+/// edges leaving R, and there is no other cut of at most the same size whose source side is a
+/// strict superset of R. The last condition holds if and only if adding any neighbor of R to the
+/// source side increases the size of the minimum cut beyond the number of edges.
+pub fn is_important_cut<G>(
+    graph: G,
+    source_set: &[usize],
+    destination_set: &[usize],
+    edges: &[usize],
+) -> bool
+where
+    G: NodeIndexable + EdgeIndexable + IntoEdgeReferences,
+{
+    let (compacted_graph, compaction) =
+        Compaction::new(graph, &[source_set, destination_set].concat());
+    let Some(compacted_edges) = compaction.compact_edges(edges) else {
+        return false;
+    };
+    let compacted_edges = compacted_edges.into_iter().collect::<HashSet<_>>();
+    let source_set = compaction.compact_vertices(source_set);
+    let destination_set = compaction.compact_vertices(destination_set);
+
+    let source_side = reachable_vertices(&compacted_graph, &source_set, |edge| {
+        !compacted_edges.contains(&edge)
+    });
+    if destination_set
+        .iter()
+        .any(|vertex| source_side.contains(vertex))
+    {
+        return false;
+    }
+
+    // every edge has to leave the source side, the edges leaving it are then exactly the given
+    // ones as the source side would be larger otherwise
+    let mut neighbors = HashSet::new();
+    for &edge in &compacted_edges {
+        let (a, b) = compacted_graph
+            .edge_endpoints(EdgeIndex::new(edge))
+            .map(|(a, b)| (a.index(), b.index()))
+            .expect("Compacted edge should exist");
+        match (source_side.contains(&a), source_side.contains(&b)) {
+            (true, false) => neighbors.insert(b),
+            (false, true) => neighbors.insert(a),
+            _ => return false,
+        };
+    }
+
+    let source_side = source_side.into_iter().collect::<Vec<_>>();
+    neighbors
+        .into_iter()
+        .filter(|neighbor| !destination_set.contains(neighbor))
+        .all(|neighbor| {
+            let extended_source_side = [source_side.clone(), vec![neighbor]].concat();
+            max_flow_value(
+                &compacted_graph,
+                extended_source_side,
+                destination_set.clone(),
+            ) > compacted_edges.len()
+        })
+}
+
+/// Same as [`is_important_cut`] for a graph with contiguous indices, where only the edges marked
+/// in `edges_in_use` are present.
+pub(crate) fn is_important_cut_with_edges_in_use(
+    graph: &UnGraph,
+    edges_in_use: &[bool],
+    source_set: &[usize],
+    destination_set: &[usize],
+    edges: &[usize],
+) -> bool {
+    if edges_in_use.iter().all(|&in_use| in_use) {
+        return is_important_cut(graph, source_set, destination_set, edges);
+    }
+
+    // the edges that are in use get new indices in a graph of their own
+    let mut present_graph = UnGraph::with_capacity(graph.node_count(), graph.edge_count());
+    for _ in 0..graph.node_count() {
+        present_graph.add_node(());
+    }
+    let mut present_edges = vec![None; graph.edge_count()];
+    for edge in graph.edge_references() {
+        if edges_in_use[edge.id().index()] {
+            present_edges[edge.id().index()] = Some(
+                present_graph
+                    .add_edge(edge.source(), edge.target(), ())
+                    .index(),
+            );
+        }
+    }
+    let Some(edges) = edges
+        .iter()
+        .map(|&edge| present_edges[edge])
+        .collect::<Option<Vec<_>>>()
+    else {
+        return false;
+    };
+    is_important_cut(&present_graph, source_set, destination_set, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use crate::cuts::cut::Cut;
+    use crate::cuts::verify::{is_important_cut, verify_cut};
+
+    #[test]
+    fn valid_cut() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        assert!(verify_cut(&graph, &cut, &[0], &[2, 3]));
+
+        let cut = Cut::new(vec![0], vec![1, 2, 3], vec![0]);
+        assert!(verify_cut(&graph, &cut, &[0], &[2, 3]));
+    }
+
+    #[test]
+    fn cut_missing_crossing_edge() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1]);
+        assert!(!verify_cut(&graph, &cut, &[0], &[2, 3]));
+    }
+
+    #[test]
+    fn cut_edge_not_crossing_partition() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![0, 1, 2]);
+        assert!(!verify_cut(&graph, &cut, &[0], &[2, 3]));
+    }
+
+    #[test]
+    fn important_cuts_of_y_shape() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        assert!(is_important_cut(&graph, &[0], &[2, 3], &[0]));
+        assert!(is_important_cut(&graph, &[0], &[2, 3], &[1, 2]));
+        assert!(is_important_cut(&graph, &[0], &[2], &[1]));
+    }
+
+    #[test]
+    fn non_important_cut_of_y_shape() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+
+        // the cut {(1, 2)} has a larger source side with the same size
+        assert!(!is_important_cut(&graph, &[0], &[2], &[0]));
+    }
+
+    #[test]
+    fn non_important_cuts() {
+        // 0 - 1 - 2 - 3
+        //      \
+        //       4
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (2, 3), (1, 4)]);
+
+        // the cut {(2, 3)} has a larger source side with the same size
+        assert!(!is_important_cut(&graph, &[0], &[3, 4], &[1, 3]));
+        assert!(is_important_cut(&graph, &[0], &[3, 4], &[2, 3]));
+        // not a cut
+        assert!(!is_important_cut(&graph, &[0], &[3, 4], &[1]));
+        // not minimal
+        assert!(!is_important_cut(&graph, &[0], &[3, 4], &[0, 2]));
+        // edge does not exist
+        assert!(!is_important_cut(&graph, &[0], &[3, 4], &[0, 7]));
+    }
+}
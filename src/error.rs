@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+/// Whether an index refers to a vertex or to an edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MappingKind {
+    Vertex,
+    Edge,
+}
+
+impl Display for MappingKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MappingKind::Vertex => write!(f, "vertex"),
+            MappingKind::Edge => write!(f, "edge"),
+        }
+    }
+}
+
+/// Errors that can be caused by the inputs of the functions of this crate, as opposed to broken
+/// invariants of the algorithms, which still panic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The edge does not exist or does not have one endpoint on each side of the cut
+    IllegalEndpoint(usize),
+    /// An index mapping has no entry for the contracted vertex or edge
+    MissingMapping { kind: MappingKind, index: usize },
+    /// The cut has no edges
+    EmptyCut,
+    /// The source set or the destination set is empty
+    EmptySet,
+    /// The vertex is both in the source set and in the destination set
+    OverlappingSets(usize),
+    /// The vertex is both the source and the destination of a flow
+    SourceEqualsDestination(usize),
+    /// A sum of edge weights or capacities does not fit into its integer type
+    Overflow,
+    /// The vertex is both on the source side and on the destination side of a cut
+    OverlappingSides(usize),
+    /// The edge is not in the graph
+    MissingEdge(usize),
+    /// The vertex of the source or destination set is not in the graph
+    MissingVertex(usize),
+    /// The vertex of the source or destination set is excluded by a node filter
+    FilteredVertex(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::IllegalEndpoint(edge) => {
+                write!(f, "Edge {} does not cross the cut", edge)
+            }
+            Error::MissingMapping { kind, index } => {
+                write!(f, "Index mapping missing entry for {} {}", kind, index)
+            }
+            Error::EmptyCut => write!(f, "The cut has no edges"),
+            Error::EmptySet => write!(f, "The source and destination sets cannot be empty"),
+            Error::OverlappingSets(vertex) => write!(
+                f,
+                "Vertex {} is in both the source and the destination set",
+                vertex
+            ),
+            Error::SourceEqualsDestination(vertex) => {
+                write!(
+                    f,
+                    "Vertex {} is both the source and the destination",
+                    vertex
+                )
+            }
+            Error::Overflow => write!(f, "The sum of the edge weights overflows"),
+            Error::OverlappingSides(vertex) => {
+                write!(f, "Vertex {} is on both sides of the cut", vertex)
+            }
+            Error::MissingEdge(edge) => write!(f, "Edge {} is not in the graph", edge),
+            Error::MissingVertex(vertex) => write!(f, "Vertex {} is not in the graph", vertex),
+            Error::FilteredVertex(vertex) => {
+                write!(f, "Vertex {} does not pass the node filter", vertex)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
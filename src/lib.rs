@@ -0,0 +1,8 @@
+pub mod cuts;
+mod error;
+pub mod visualization;
+
+pub use error::{Error, MappingKind};
+
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+mod web;
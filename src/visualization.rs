@@ -1,3 +1,22 @@
+mod animation;
 mod app;
+mod color;
 mod edge;
+#[cfg(test)]
+mod headless;
+mod layout;
 mod node;
+mod options;
+#[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+mod persistence;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use app::draw_graph;
+#[cfg(not(target_arch = "wasm32"))]
+pub use app::draw_graph_with_cuts;
+#[cfg(not(target_arch = "wasm32"))]
+pub use app::draw_graph_with_paths;
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub use app::start_web;
+pub use color::ColorScheme;
+pub use options::VizOptions;
@@ -0,0 +1,130 @@
+use crate::cuts::Path;
+
+/// Time it takes the flow front to pass over one edge, in seconds.
+const SECONDS_PER_EDGE: f32 = 0.5;
+
+/// How far the flow has been pushed along the augmenting paths, which are saturated one after the
+/// other, from the source to the destination.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Progress {
+    /// The paths before `path` are saturated, and so are the first `saturated_edges` edges of it
+    Pushing { path: usize, saturated_edges: usize },
+    /// All paths are saturated
+    Done,
+}
+
+impl Progress {
+    /// Number of saturated edges at the start of the path with the given index and length.
+    pub(crate) fn saturated_edges_of(&self, path_index: usize, path_length: usize) -> usize {
+        match *self {
+            Progress::Pushing {
+                path,
+                saturated_edges,
+            } if path == path_index => saturated_edges,
+            Progress::Pushing { path, .. } if path < path_index => 0,
+            _ => path_length,
+        }
+    }
+}
+
+/// Progress of the flow front after the given number of seconds, when the paths have the given
+/// numbers of edges.
+pub(crate) fn progress(path_lengths: &[usize], elapsed: f32) -> Progress {
+    let mut edges_passed = (elapsed.max(0.) / SECONDS_PER_EDGE) as usize;
+    for (path, &path_length) in path_lengths.iter().enumerate() {
+        if edges_passed < path_length {
+            return Progress::Pushing {
+                path,
+                saturated_edges: edges_passed,
+            };
+        }
+        edges_passed -= path_length;
+    }
+    Progress::Done
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum AnimationState {
+    /// The flow front has been moving for `elapsed` seconds
+    Running { elapsed: f32 },
+    /// All paths are saturated and the cut is shown
+    Finished,
+}
+
+/// Animation of a maximum flow that is pushed along its augmenting paths one at a time, after
+/// which the cut is shown.
+#[derive(Debug)]
+pub(crate) struct FlowAnimation {
+    pub(crate) paths: Vec<Path>,
+    pub(crate) state: AnimationState,
+}
+
+impl FlowAnimation {
+    pub(crate) fn new(paths: Vec<Path>) -> Self {
+        Self {
+            paths,
+            state: AnimationState::Running { elapsed: 0. },
+        }
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        matches!(self.state, AnimationState::Running { .. })
+    }
+
+    /// Moves the flow front forward by the time of a frame and returns the progress after it.
+    pub(crate) fn advance(&mut self, frame_time: f32) -> Progress {
+        let AnimationState::Running { elapsed } = self.state else {
+            return Progress::Done;
+        };
+        let elapsed = elapsed + frame_time;
+        let path_lengths = self.paths.iter().map(|p| p.edges.len()).collect::<Vec<_>>();
+        let progress = progress(&path_lengths, elapsed);
+        self.state = match progress {
+            Progress::Done => AnimationState::Finished,
+            Progress::Pushing { .. } => AnimationState::Running { elapsed },
+        };
+        progress
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cuts::Path;
+    use crate::visualization::animation::{
+        progress, AnimationState, FlowAnimation, Progress, SECONDS_PER_EDGE,
+    };
+
+    #[test]
+    fn progress_after_elapsed_time() {
+        let path_lengths = [2, 3];
+        let pushing = |path, saturated_edges| Progress::Pushing {
+            path,
+            saturated_edges,
+        };
+
+        // the elapsed time is given as the number of edges the flow front has passed
+        let after = |edges: f32| progress(&path_lengths, edges * SECONDS_PER_EDGE);
+
+        assert_eq!(pushing(0, 0), after(0.));
+        assert_eq!(pushing(0, 1), after(1.5));
+        assert_eq!(pushing(1, 0), after(2.));
+        assert_eq!(pushing(1, 2), after(4.5));
+        assert_eq!(Progress::Done, after(5.));
+
+        let progress = pushing(1, 2);
+        assert_eq!(2, progress.saturated_edges_of(0, 2));
+        assert_eq!(2, progress.saturated_edges_of(1, 3));
+        assert_eq!(0, progress.saturated_edges_of(2, 4));
+    }
+
+    #[test]
+    fn animation_finishes_after_all_edges() {
+        let paths = vec![Path::new(vec![0, 1, 2], vec![0, 1])];
+        let mut animation = FlowAnimation::new(paths);
+
+        animation.advance(SECONDS_PER_EDGE);
+        assert!(animation.is_running());
+        assert_eq!(Progress::Done, animation.advance(SECONDS_PER_EDGE));
+        assert_eq!(AnimationState::Finished, animation.state);
+    }
+}
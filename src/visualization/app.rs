@@ -1,20 +1,101 @@
-use crate::cuts::Cut;
-use crate::visualization::edge::{CustomEdgeShape, EdgeData};
-use crate::visualization::node::{CustomNodeShape, NodeData};
-use eframe::{run_native, App, CreationContext};
-use egui::{Context, Style, Visuals};
-use egui_graphs;
-use egui_graphs::{GraphView, SettingsInteraction, SettingsStyle};
-use petgraph;
+use crate::cuts::{Cut, IndexMapping, Path};
+use crate::visualization::animation::{FlowAnimation, Progress};
+use crate::visualization::color::ColorScheme;
+use crate::visualization::edge::{CustomEdgeShape, EdgeData, SeparatorInfo};
+use crate::visualization::layout::Layout;
+use crate::visualization::node::{CustomNodeShape, NodeData, NodeType};
+use crate::visualization::options::VizOptions;
+use eframe::{App, AppCreator};
+use egui::{Align2, Context, FontId, Id, InputState, Key, Pos2, Style, Vec2, Visuals};
+use egui_graphs::{GraphView, Metadata, SettingsInteraction, SettingsNavigation, SettingsStyle};
+use itertools::Itertools;
 use petgraph::prelude::StableUnGraph;
-use petgraph::stable_graph::DefaultIx;
+use petgraph::stable_graph::{DefaultIx, EdgeIndex, NodeIndex};
 use petgraph::visit::{EdgeIndexable, EdgeRef};
 use petgraph::Undirected;
 
 // TODO Implement toggling between directed and undirected graphs e.g. via generics
 
-struct GraphApp {
-    graph: egui_graphs::Graph<
+/// Node or edge of the visualized graph that the pointer is over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Hovered {
+    Node(NodeIndex),
+    Edge(EdgeIndex),
+}
+
+/// What the app can be told to do with the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Action {
+    PreviousCut,
+    NextCut,
+    FitView,
+    ResetLayout,
+}
+
+impl Action {
+    /// Action of a key: the left and right arrows move between the cuts, `F` fits the view to the
+    /// screen and `R` resets the layout.
+    fn of_key(key: Key) -> Option<Self> {
+        match key {
+            Key::ArrowLeft => Some(Action::PreviousCut),
+            Key::ArrowRight => Some(Action::NextCut),
+            Key::F => Some(Action::FitView),
+            Key::R => Some(Action::ResetLayout),
+            _ => None,
+        }
+    }
+}
+
+/// Actions of the keys pressed during a frame, in the order the keys were pressed.
+pub(crate) fn keyboard_actions(input: &InputState) -> Vec<Action> {
+    input
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } if modifiers.is_none() => Action::of_key(*key),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Pan and zoom of the view of the graph, which are kept so that the view stays the same when the
+/// graph is rebuilt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Camera {
+    pub(crate) zoom: f32,
+    pub(crate) pan: Vec2,
+    /// Top left corner of the graph widget, which the pan is relative to
+    pub(crate) top_left: Pos2,
+}
+
+impl Camera {
+    fn of(meta: &Metadata) -> Self {
+        Self {
+            zoom: meta.zoom,
+            pan: meta.pan,
+            top_left: meta.top_left,
+        }
+    }
+
+    /// Writes the camera into the metadata of the graph view, which also keeps the view from being
+    /// fitted to the screen as on the first frame.
+    fn store_into_ui(self, ui: &mut egui::Ui) {
+        let mut meta = Metadata::get(ui);
+        meta.first_frame = false;
+        meta.zoom = self.zoom;
+        meta.pan = self.pan;
+        meta.top_left = self.top_left;
+        meta.store_into_ui(ui);
+    }
+}
+
+pub(crate) struct GraphApp {
+    pub(crate) graph: egui_graphs::Graph<
         NodeData,
         EdgeData,
         Undirected,
@@ -22,86 +103,896 @@ struct GraphApp {
         CustomNodeShape,
         CustomEdgeShape,
     >,
+    pub(crate) layout: Layout,
+    /// Cuts that can be moved between, of which the one at `cut_index` is highlighted
+    pub(crate) cuts: Vec<Cut>,
+    pub(crate) cut_index: usize,
+    /// Whether the dark or the light theme of egui is used
+    pub(crate) dark_mode: bool,
+    /// Whether the edge indices and the number of cuts are drawn on top of the graph
+    pub(crate) debug_overlay: bool,
+    /// Whether the view has to be fitted to the screen on the next frame
+    fit_view: bool,
+    /// Animation of the flow along the augmenting paths, which is kept after it has finished so
+    /// that it can be replayed
+    pub(crate) flow_animation: Option<FlowAnimation>,
+    /// Camera of the last drawn frame
+    pub(crate) camera: Option<Camera>,
+    /// Whether the camera has to be restored on the next frame because the graph was rebuilt
+    pub(crate) restore_camera: bool,
+    /// Colors of the graph, which are kept to draw a graph that is loaded from a file
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    pub(crate) color_scheme: ColorScheme,
+    /// Path of the file the graph is saved to and loaded from
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    state_path: String,
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    state_error: Option<String>,
 }
 
 impl GraphApp {
-    #[allow(dead_code)]
     pub(crate) fn new(
         graph: petgraph::Graph<(), (), Undirected>,
         cut: Cut,
-        _: &CreationContext<'_>,
+        index_mapping: Option<IndexMapping>,
+        color_scheme: ColorScheme,
     ) -> Self {
+        let layout = Layout::default();
         Self {
-            graph: generate_graph(&graph, cut),
+            graph: generate_graph(
+                &graph,
+                cut.clone(),
+                index_mapping.as_ref(),
+                layout,
+                color_scheme,
+            ),
+            layout,
+            cuts: vec![cut],
+            cut_index: 0,
+            dark_mode: true,
+            debug_overlay: false,
+            fit_view: false,
+            flow_animation: None,
+            camera: None,
+            restore_camera: false,
+            #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+            color_scheme,
+            #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+            state_path: "graph.json".to_string(),
+            #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+            state_error: None,
         }
     }
-}
 
-impl App for GraphApp {
-    fn update(&mut self, ctx: &Context, _: &mut eframe::Frame) {
+    /// Replaces the cuts that can be moved between and highlights the first one.
+    ///
+    /// Panics if there are no cuts.
+    pub(crate) fn set_cuts(&mut self, cuts: Vec<Cut>) {
+        assert!(!cuts.is_empty(), "There should be at least one cut");
+        self.cuts = cuts;
+        self.show_cut(0);
+    }
+
+    /// Highlights the cut at the given index. Only the colors of the nodes and edges change, so
+    /// the positions of the nodes and the view stay the same.
+    pub(crate) fn show_cut(&mut self, cut_index: usize) {
+        let Some(cut) = self.cuts.get(cut_index) else {
+            return;
+        };
+        self.cut_index = cut_index;
+
+        let node_types = node_types(cut, self.graph.node_count());
+        for (node_index, node_type) in node_types.into_iter().enumerate() {
+            if let Some(node) = self.graph.node_mut(NodeIndex::new(node_index)) {
+                node.payload_mut().set_node_type(node_type);
+            }
+        }
+        let edge_indices = self
+            .graph
+            .edges_iter()
+            .map(|(edge_index, _)| edge_index)
+            .collect::<Vec<_>>();
+        for edge_index in edge_indices {
+            let is_separator = cut.cut_edge_set.contains(&edge_index.index());
+            if let Some(edge) = self.graph.edge_mut(edge_index) {
+                edge.payload_mut().set_is_separator(is_separator);
+            }
+        }
+    }
+
+    /// Does what the action says. Moving between the cuts wraps around at either end.
+    pub(crate) fn apply(&mut self, action: Action) {
+        let cut_count = self.cuts.len();
+        match action {
+            Action::PreviousCut => self.show_cut((self.cut_index + cut_count - 1) % cut_count),
+            Action::NextCut => self.show_cut((self.cut_index + 1) % cut_count),
+            Action::FitView => self.fit_view = true,
+            Action::ResetLayout => self.reset_layout(),
+        }
+    }
+
+    /// Moves every node back to its position in the chosen layout. Everything else, such as the
+    /// source and destination sets, stays the same.
+    pub(crate) fn reset_layout(&mut self) {
+        let positions = self.layout.positions(self.graph.node_count());
+        for (node_index, position) in positions.into_iter().enumerate() {
+            if let Some(node) = self.graph.node_mut(NodeIndex::new(node_index)) {
+                node.set_location(position);
+            }
+        }
+    }
+
+    /// Highlights the saturated edges of each of the augmenting paths in a color of its own, see
+    /// [`augmenting_paths`](crate::cuts::augmenting_paths). The edge indices of the paths have to
+    /// be the ones of the visualized graph. Edges that are not saturated are no longer
+    /// highlighted, and the cut edges are only shown once all paths are saturated.
+    pub(crate) fn highlight_flow(&mut self, paths: &[Path], progress: Progress) {
+        let cut_edges = &self.cuts[self.cut_index].cut_edge_set;
+        let edges = self
+            .graph
+            .edges_iter()
+            .map(|(edge_index, _)| {
+                let path_id = paths.iter().enumerate().position(|(path_index, path)| {
+                    let saturated_edges = progress.saturated_edges_of(path_index, path.edges.len());
+                    path.edges[..saturated_edges].contains(&edge_index.index())
+                });
+                let is_separator =
+                    progress == Progress::Done && cut_edges.contains(&edge_index.index());
+                (edge_index, path_id, is_separator)
+            })
+            .collect::<Vec<_>>();
+        for (edge_index, path_id, is_separator) in edges {
+            if let Some(edge) = self.graph.edge_mut(edge_index) {
+                edge.payload_mut().set_path_id(path_id);
+                edge.payload_mut().set_is_separator(is_separator);
+            }
+        }
+    }
+
+    /// Starts pushing flow along the augmenting paths one at a time, after which the cut is shown.
+    pub(crate) fn animate_flow(&mut self, paths: Vec<Path>) {
+        let animation = FlowAnimation::new(paths);
+        self.highlight_flow(
+            &animation.paths,
+            Progress::Pushing {
+                path: 0,
+                saturated_edges: 0,
+            },
+        );
+        self.flow_animation = Some(animation);
+    }
+
+    /// Moves the running flow animation forward by the time of a frame.
+    fn advance_flow(&mut self, frame_time: f32) {
+        let Some(mut animation) = self.flow_animation.take() else {
+            return;
+        };
+        if animation.is_running() {
+            let progress = animation.advance(frame_time);
+            self.highlight_flow(&animation.paths, progress);
+        }
+        self.flow_animation = Some(animation);
+    }
+
+    /// Finds the node or the edge at the given screen position, where nodes take precedence over
+    /// the edges that end in them.
+    pub(crate) fn hovered(&self, meta: &Metadata, screen_pos: Pos2) -> Option<Hovered> {
+        self.graph
+            .node_by_screen_pos(meta, screen_pos)
+            .map(Hovered::Node)
+            .or_else(|| {
+                self.graph
+                    .edge_by_screen_pos(meta, screen_pos)
+                    .map(Hovered::Edge)
+            })
+    }
+
+    /// Text of the tooltip of a hovered node or edge. A node is described by its index and, if
+    /// the graph has been contracted, its original vertices, and an edge by its endpoints and
+    /// whether it is a cut edge.
+    fn hover_text(&self, hovered: Hovered) -> Option<String> {
+        match hovered {
+            Hovered::Node(node_index) => {
+                let label = self.graph.node(node_index)?.label();
+                let index = node_index.index();
+                Some(match label == index.to_string() {
+                    true => format!("Node {}", index),
+                    false => format!("Node {} (vertices {})", index, label),
+                })
+            }
+            Hovered::Edge(edge_index) => {
+                let (source, target) = self.graph.edge_endpoints(edge_index)?;
+                let kind = match self.graph.edge(edge_index)?.payload().get_is_separator() {
+                    true => "cut edge",
+                    false => "not a cut edge",
+                };
+                Some(format!(
+                    "Edge {} ({}, {}), {}",
+                    edge_index.index(),
+                    source.index(),
+                    target.index(),
+                    kind
+                ))
+            }
+        }
+    }
+
+    /// Draws the controls for choosing the layout and resetting the nodes to it.
+    fn layout_controls(&mut self, ui: &mut egui::Ui) {
+        egui::ComboBox::from_label("Layout")
+            .selected_text(self.layout.to_string())
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.layout, Layout::Circular, "Circular");
+                if !matches!(self.layout, Layout::Random { .. }) {
+                    ui.selectable_value(&mut self.layout, Layout::Random { seed: 0 }, "Random");
+                }
+            });
+        if let Layout::Random { seed } = &mut self.layout {
+            ui.add(egui::DragValue::new(seed).prefix("Seed: "));
+        }
+        if ui
+            .button("Reset layout")
+            .on_hover_text("Shortcut: R")
+            .clicked()
+        {
+            self.apply(Action::ResetLayout);
+        }
+        if ui.button("Fit view").on_hover_text("Shortcut: F").clicked() {
+            self.apply(Action::FitView);
+        }
+        ui.checkbox(&mut self.dark_mode, "Dark mode");
+        ui.checkbox(&mut self.debug_overlay, "Debug overlay");
+    }
+
+    /// Draws the index of each edge at its midpoint and the number of cuts in the top right corner
+    /// of the graph view.
+    fn debug_overlay(&self, ui: &egui::Ui, meta: &Metadata) {
+        let painter = ui.painter_at(ui.max_rect());
+        let color = ui.visuals().strong_text_color();
+        let font = FontId::monospace(12.);
+        for (edge_index, _) in self.graph.edges_iter() {
+            let Some((source, target)) = self.graph.edge_endpoints(edge_index) else {
+                continue;
+            };
+            let (Some(source), Some(target)) = (self.graph.node(source), self.graph.node(target))
+            else {
+                continue;
+            };
+            let midpoint = source.location().lerp(target.location(), 0.5);
+            painter.text(
+                meta.canvas_to_screen_pos(midpoint),
+                Align2::CENTER_CENTER,
+                edge_index.index().to_string(),
+                font.clone(),
+                color,
+            );
+        }
+
+        let cut_count = match self.cuts.len() {
+            1 => "1 important cut found".to_string(),
+            cut_count => format!("{} important cuts found", cut_count),
+        };
+        painter.text(
+            ui.max_rect().right_top(),
+            Align2::RIGHT_TOP,
+            cut_count,
+            font,
+            color,
+        );
+    }
+
+    /// Switches to the theme chosen with `dark_mode` if the context does not use it yet. The
+    /// colors of the nodes and edges that are not highlighted are taken from the theme.
+    fn apply_theme(&self, ctx: &Context) {
+        if ctx.style().visuals.dark_mode == self.dark_mode {
+            return;
+        }
+        let visuals = match self.dark_mode {
+            true => Visuals::dark(),
+            false => Visuals::light(),
+        };
+        ctx.set_style(Style {
+            visuals,
+            ..(*ctx.style()).clone()
+        });
+    }
+
+    /// Draws the controls for moving between the cuts.
+    fn cut_controls(&mut self, ui: &mut egui::Ui) {
+        let has_other_cuts = self.cuts.len() > 1;
+        if ui
+            .add_enabled(has_other_cuts, egui::Button::new("Previous cut"))
+            .on_hover_text("Shortcut: Left arrow")
+            .clicked()
+        {
+            self.apply(Action::PreviousCut);
+        }
+        ui.label(format!("Cut {}/{}", self.cut_index + 1, self.cuts.len()));
+        if ui
+            .add_enabled(has_other_cuts, egui::Button::new("Next cut"))
+            .on_hover_text("Shortcut: Right arrow")
+            .clicked()
+        {
+            self.apply(Action::NextCut);
+        }
+        if self.flow_animation.is_some() && ui.button("Animate flow").clicked() {
+            if let Some(animation) = self.flow_animation.take() {
+                self.animate_flow(animation.paths);
+            }
+        }
+    }
+
+    /// Draws the controls for saving the graph to and loading it from a JSON file.
+    #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+    fn persistence_controls(&mut self, ui: &mut egui::Ui) {
+        ui.text_edit_singleline(&mut self.state_path);
+        if ui.button("Save").clicked() {
+            self.state_error = self
+                .save_to_file(&self.state_path)
+                .err()
+                .map(|e| e.to_string());
+        }
+        if ui.button("Load").clicked() {
+            let state_path = self.state_path.clone();
+            self.state_error = self.load_from_file(state_path).err().map(|e| e.to_string());
+        }
+        if let Some(error) = &self.state_error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        }
+    }
+
+    /// Draws one frame of the app. Kept separate from [`App::update`] so that it can be driven
+    /// without a window.
+    pub(crate) fn ui(&mut self, ctx: &Context) {
         let settings_style = &SettingsStyle::new().with_labels_always(true);
         let interaction_settings = &SettingsInteraction::new()
             .with_dragging_enabled(true)
             .with_node_clicking_enabled(true)
             .with_node_selection_enabled(true);
+        // The graph is only fitted to the screen on the first frame, after which the camera is
+        // moved by zooming and panning
+        let navigation_settings = &SettingsNavigation::new()
+            .with_fit_to_screen_enabled(false)
+            .with_zoom_and_pan_enabled(true);
+
+        self.apply_theme(ctx);
+
+        if self
+            .flow_animation
+            .as_ref()
+            .is_some_and(FlowAnimation::is_running)
+        {
+            self.advance_flow(ctx.input(|input| input.stable_dt));
+            ctx.request_repaint();
+        }
+
+        // Keys typed into a text field are not shortcuts
+        if !ctx.wants_keyboard_input() {
+            for action in ctx.input(keyboard_actions) {
+                self.apply(action);
+            }
+        }
+
+        egui::TopBottomPanel::top("controls").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                self.cut_controls(ui);
+                ui.separator();
+                self.layout_controls(ui);
+                #[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+                {
+                    ui.separator();
+                    self.persistence_controls(ui);
+                }
+            });
+        });
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            if self.restore_camera {
+                if let Some(camera) = self.camera {
+                    camera.store_into_ui(ui);
+                }
+                self.restore_camera = false;
+            }
+            if self.fit_view {
+                // The view is fitted to the screen on the first frame of the graph view
+                Metadata::default().store_into_ui(ui);
+                self.fit_view = false;
+            }
             ui.add(
                 &mut GraphView::<_, _, _, _, CustomNodeShape, CustomEdgeShape>::new(
                     &mut self.graph,
                 )
                 .with_styles(settings_style)
-                .with_interactions(interaction_settings),
+                .with_interactions(interaction_settings)
+                .with_navigations(navigation_settings),
             );
+
+            let meta = Metadata::get(ui);
+            self.camera = Some(Camera::of(&meta));
+            if self.debug_overlay {
+                self.debug_overlay(ui, &meta);
+            }
+            let hover_text = ctx
+                .pointer_hover_pos()
+                .and_then(|pos| self.hovered(&meta, pos))
+                .and_then(|hovered| self.hover_text(hovered));
+            if let Some(text) = hover_text {
+                egui::show_tooltip_at_pointer(ctx, Id::new("graph_hover"), |ui| {
+                    ui.label(text);
+                });
+            }
         });
     }
 }
 
-fn generate_graph(
+impl App for GraphApp {
+    fn update(&mut self, ctx: &Context, _: &mut eframe::Frame) {
+        self.ui(ctx);
+    }
+}
+
+/// Label of a node, which lists the original vertices of the node if the graph has been
+/// contracted, e.g. "{0,1}" for a node that two vertices were contracted into.
+fn node_label(node_index: usize, index_mapping: Option<&IndexMapping>) -> String {
+    match index_mapping.and_then(|mapping| mapping.original_vertices(node_index)) {
+        Some(original_vertices) if original_vertices.len() == 1 => original_vertices[0].to_string(),
+        Some(original_vertices) => format!("{{{}}}", original_vertices.iter().join(",")),
+        None => node_index.to_string(),
+    }
+}
+
+/// Side of the cut each of the nodes is on. Nodes on neither side, e.g. isolated vertices that are
+/// left out of cuts, are drawn in the neutral color of the style.
+fn node_types(cut: &Cut, node_count: usize) -> Vec<NodeType> {
+    let mut node_types = vec![NodeType::Other; node_count];
+    for &vertex in &cut.destination_set {
+        if let Some(node_type) = node_types.get_mut(vertex) {
+            *node_type = NodeType::Destination;
+        }
+    }
+    for &vertex in &cut.source_set {
+        if let Some(node_type) = node_types.get_mut(vertex) {
+            *node_type = NodeType::Source;
+        }
+    }
+    node_types
+}
+
+pub(crate) fn generate_graph(
     graph: &petgraph::Graph<(), (), Undirected>,
     cut: Cut,
+    index_mapping: Option<&IndexMapping>,
+    layout: Layout,
+    color_scheme: ColorScheme,
 ) -> egui_graphs::Graph<NodeData, EdgeData, Undirected, DefaultIx, CustomNodeShape, CustomEdgeShape>
 {
     let node_count = graph.node_count();
     let edge_count = graph.edge_count();
     let mut g = StableUnGraph::with_capacity(node_count, edge_count);
 
+    let mut degrees = vec![0; node_count];
+    for edge in graph.edge_references() {
+        degrees[edge.source().index()] += 1;
+        degrees[edge.target().index()] += 1;
+    }
+
+    let node_types = node_types(&cut, node_count);
     (0usize..node_count).for_each(|node_index| {
-        // Color vertices according to the cut
-        if cut.source_set.contains(&node_index) {
-            g.add_node(NodeData::new_source());
-        } else if cut.destination_set.contains(&node_index) {
-            g.add_node(NodeData::new_destination());
-        } else {
-            // This is unreachable for now, but we'll keep it for when cuts change to separators
-            g.add_node(NodeData::new());
-        }
+        let node_data = match node_types[node_index] {
+            NodeType::Source => NodeData::new_source(),
+            NodeType::Destination => NodeData::new_destination(),
+            NodeType::Other => NodeData::new(),
+        };
+        g.add_node(
+            node_data
+                .with_color_scheme(color_scheme)
+                .with_degree(degrees[node_index]),
+        );
     });
 
     graph.edge_references().for_each(|edge| {
         let edge_id = EdgeIndexable::to_index(&graph, edge.id());
         let is_colored = cut.cut_edge_set.contains(&edge_id);
-        g.add_edge(edge.source(), edge.target(), EdgeData::new(is_colored));
+        // An edge of a contracted graph has the capacity of the edges it was contracted from
+        let capacity = index_mapping
+            .and_then(|mapping| mapping.original_edges(edge_id))
+            .map_or(1, <[usize]>::len);
+        let edge_data = EdgeData::new(is_colored)
+            .with_color_scheme(color_scheme)
+            .with_capacity(capacity);
+        g.add_edge(edge.source(), edge.target(), edge_data);
     });
 
-    egui_graphs::Graph::from(&g)
+    let mut egui_graph = egui_graphs::Graph::from(&g);
+    let positions = layout.positions(node_count);
+    (0usize..node_count).for_each(|node_index| {
+        if let Some(node) = egui_graph.node_mut(NodeIndex::new(node_index)) {
+            node.set_label(node_label(node_index, index_mapping));
+            node.set_location(positions[node_index]);
+        }
+    });
+    egui_graph
 }
 
-#[allow(dead_code)]
-pub fn draw_graph(graph: petgraph::Graph<(), (), Undirected>, cut: Cut) {
-    let native_options = eframe::NativeOptions::default();
-    run_native(
-        "Important Separator Project",
+/// Creates the app shared by the native and the web entry points.
+fn app_creator(
+    graph: petgraph::Graph<(), (), Undirected>,
+    cuts: Vec<Cut>,
+    index_mapping: Option<IndexMapping>,
+    color_scheme: ColorScheme,
+    paths: Vec<Path>,
+    options: VizOptions,
+) -> AppCreator {
+    Box::new(move |cc| {
+        let mut app = GraphApp::new(graph, cuts[0].clone(), index_mapping, color_scheme);
+        app.dark_mode = options.dark_mode;
+        app.debug_overlay = options.debug_overlay;
+        app.apply_theme(&cc.egui_ctx);
+        app.set_cuts(cuts);
+        if !paths.is_empty() {
+            app.animate_flow(paths);
+        }
+        Box::new(app)
+    })
+}
+
+/// Opens a window with the given options that draws the graph.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_native(
+    graph: petgraph::Graph<(), (), Undirected>,
+    cuts: Vec<Cut>,
+    index_mapping: Option<IndexMapping>,
+    color_scheme: ColorScheme,
+    paths: Vec<Path>,
+    options: VizOptions,
+) {
+    let title = options.title.clone();
+    let native_options = options.native_options();
+    eframe::run_native(
+        &title,
         native_options,
-        Box::new(|cc| {
-            // Set to dark mode always
-            let style = Style {
-                visuals: Visuals::dark(),
-                ..Style::default()
-            };
-            cc.egui_ctx.set_style(style);
-            Box::new(GraphApp::new(graph, cut, cc))
-        }),
+        app_creator(graph, cuts, index_mapping, color_scheme, paths, options),
     )
     .unwrap();
 }
+
+/// Draws the graph and highlights the given cut in the colors of the color scheme. If the graph is
+/// a contracted graph, the index mapping can be passed to label the nodes with their original
+/// vertices. The options set up the window, use [`VizOptions::default`] for the defaults.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn draw_graph(
+    graph: petgraph::Graph<(), (), Undirected>,
+    cut: Cut,
+    index_mapping: Option<IndexMapping>,
+    color_scheme: ColorScheme,
+    options: VizOptions,
+) {
+    draw_graph_with_paths(graph, cut, index_mapping, color_scheme, vec![], options);
+}
+
+/// Same as [`draw_graph`], but first animates the flow being pushed along each of the augmenting
+/// paths, after which the edges of each path stay highlighted in a color of its own, while the cut
+/// edges keep their color. The paths can be found with
+/// [`augmenting_paths`](crate::cuts::augmenting_paths) on the same graph.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn draw_graph_with_paths(
+    graph: petgraph::Graph<(), (), Undirected>,
+    cut: Cut,
+    index_mapping: Option<IndexMapping>,
+    color_scheme: ColorScheme,
+    paths: Vec<Path>,
+    options: VizOptions,
+) {
+    run_native(
+        graph,
+        vec![cut],
+        index_mapping,
+        color_scheme,
+        paths,
+        options,
+    );
+}
+
+/// Same as [`draw_graph`], but for several cuts of the graph, e.g. the important cuts found by
+/// [`important_cuts_full`](crate::cuts::important_cuts_full). The cuts are highlighted one at a
+/// time, and the left and right arrow keys move between them.
+///
+/// Panics if there are no cuts.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn draw_graph_with_cuts(
+    graph: petgraph::Graph<(), (), Undirected>,
+    cuts: Vec<Cut>,
+    index_mapping: Option<IndexMapping>,
+    color_scheme: ColorScheme,
+    options: VizOptions,
+) {
+    run_native(graph, cuts, index_mapping, color_scheme, vec![], options);
+}
+
+/// Starts the visualization on the canvas with the given id. Only available for the web build,
+/// where the title and the size of the options are not used.
+#[cfg(all(feature = "web", target_arch = "wasm32"))]
+pub async fn start_web(
+    canvas_id: &str,
+    graph: petgraph::Graph<(), (), Undirected>,
+    cut: Cut,
+    index_mapping: Option<IndexMapping>,
+    color_scheme: ColorScheme,
+    options: VizOptions,
+) -> Result<(), wasm_bindgen::JsValue> {
+    eframe::WebRunner::new()
+        .start(
+            canvas_id,
+            eframe::WebOptions::default(),
+            app_creator(
+                graph,
+                vec![cut],
+                index_mapping,
+                color_scheme,
+                vec![],
+                options,
+            ),
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use egui::{Color32, Context, Event, Key, Modifiers, Pos2, RawInput, Shape, Vec2, Visuals};
+    use egui_graphs::Metadata;
+    use petgraph::graph::UnGraph;
+    use petgraph::stable_graph::{EdgeIndex, NodeIndex};
+
+    use crate::cuts::{minimum_cut, Cut, IndexMapping};
+    use crate::visualization::app::{generate_graph, keyboard_actions, Action, GraphApp, Hovered};
+    use crate::visualization::color::ColorScheme;
+    use crate::visualization::edge::SeparatorInfo;
+    use crate::visualization::headless;
+    use crate::visualization::layout::Layout;
+
+    #[test]
+    fn only_cut_edges_are_drawn_as_separators() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+
+        let shapes = headless::render_frame(&mut app);
+        let strokes = headless::line_segment_strokes(&shapes);
+
+        assert_eq!(3, strokes.len());
+        assert_eq!(
+            2,
+            strokes
+                .iter()
+                .filter(|stroke| stroke.color == ColorScheme::default().separator)
+                .count()
+        );
+    }
+
+    #[test]
+    fn separators_use_color_of_scheme() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let color_scheme = ColorScheme {
+            separator: Color32::from_rgb(0xFF, 0xA5, 0x00),
+            ..ColorScheme::default()
+        };
+        let mut app = GraphApp::new(graph, cut, None, color_scheme);
+
+        let shapes = headless::render_frame(&mut app);
+        let separator_colors = headless::line_segment_strokes(&shapes)
+            .into_iter()
+            .filter(|stroke| stroke.color == color_scheme.separator)
+            .count();
+        assert_eq!(2, separator_colors);
+    }
+
+    #[test]
+    fn right_arrow_moves_to_next_cut() {
+        let key_press = Event::Key {
+            key: Key::ArrowRight,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        };
+        let input = RawInput {
+            events: vec![key_press],
+            ..Default::default()
+        };
+
+        let mut actions = vec![];
+        let _ = Context::default().run(input, |ctx| actions = ctx.input(keyboard_actions));
+
+        assert_eq!(vec![Action::NextCut], actions);
+    }
+
+    #[test]
+    fn moving_between_cuts_recolors_edges() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let cuts = vec![
+            Cut::new(vec![0], vec![1, 2], vec![0]),
+            Cut::new(vec![0, 1], vec![2], vec![1]),
+        ];
+        let mut app = GraphApp::new(graph, cuts[0].clone(), None, ColorScheme::default());
+        app.set_cuts(cuts);
+
+        app.apply(Action::NextCut);
+        let separators = app
+            .graph
+            .edges_iter()
+            .filter(|(_, edge)| edge.payload().get_is_separator())
+            .map(|(edge_index, _)| edge_index.index())
+            .collect::<Vec<_>>();
+        assert_eq!(vec![1], separators);
+
+        // moving past the last cut wraps around to the first one
+        app.apply(Action::NextCut);
+        assert_eq!(0, app.cut_index);
+        app.apply(Action::PreviousCut);
+        assert_eq!(1, app.cut_index);
+    }
+
+    #[test]
+    fn plain_edges_follow_theme() {
+        for (dark_mode, visuals) in [(true, Visuals::dark()), (false, Visuals::light())] {
+            let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+            let cut = Cut::new(vec![0], vec![1, 2], vec![0]);
+            let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+            app.dark_mode = dark_mode;
+
+            let shapes = headless::render_frame(&mut app);
+            let colors = headless::line_segment_strokes(&shapes)
+                .into_iter()
+                .map(|stroke| stroke.color)
+                .collect::<Vec<_>>();
+
+            let plain = visuals.widgets.inactive.fg_stroke.color;
+            assert_eq!(vec![ColorScheme::default().separator, plain], colors);
+        }
+    }
+
+    #[test]
+    fn debug_overlay_labels_every_edge() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        let text_count = |shapes: Vec<Shape>| {
+            shapes
+                .iter()
+                .filter(|shape| matches!(shape, Shape::Text(_)))
+                .count()
+        };
+
+        let without_overlay = text_count(headless::render_frame(&mut app));
+        app.debug_overlay = true;
+        let with_overlay = text_count(headless::render_frame(&mut app));
+
+        // one label for each of the three edges and one for the number of cuts
+        assert_eq!(without_overlay + 4, with_overlay);
+    }
+
+    #[test]
+    fn isolated_node_is_drawn_in_neutral_color() {
+        let mut graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        graph.add_node(());
+        // minimum cuts leave out vertices that are not incident to any edge
+        let cut = minimum_cut(&graph, vec![0], vec![2]).unwrap();
+        assert!(!cut.source_set.contains(&3) && !cut.destination_set.contains(&3));
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+
+        let shapes = headless::render_frame(&mut app);
+        let fills = shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::Circle(circle) => Some(circle.fill),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let neutral = Visuals::dark().widgets.inactive.fg_stroke.color;
+        assert_eq!(4, fills.len());
+        assert_eq!(1, fills.iter().filter(|&&fill| fill == neutral).count());
+    }
+
+    #[test]
+    fn contracted_node_label_lists_original_vertices() {
+        let contracted_graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 2)]);
+        let cut = Cut::new(vec![0, 1], vec![2], vec![1, 2]);
+        let index_mapping = IndexMapping::from(
+            HashMap::from([(0, vec![0, 1]), (1, vec![2]), (2, vec![3, 4])]),
+            HashMap::from([(0, vec![1]), (1, vec![2, 3]), (2, vec![4])]),
+        );
+
+        let graph = generate_graph(
+            &contracted_graph,
+            cut.clone(),
+            Some(&index_mapping),
+            Layout::default(),
+            ColorScheme::default(),
+        );
+        let labels = (0..3)
+            .map(|node_index| graph.node(NodeIndex::new(node_index)).unwrap().label())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["{0,1}", "2", "{3,4}"], labels);
+
+        let graph = generate_graph(
+            &contracted_graph,
+            cut,
+            None,
+            Layout::default(),
+            ColorScheme::default(),
+        );
+        let labels = (0..3)
+            .map(|node_index| graph.node(NodeIndex::new(node_index)).unwrap().label())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["0", "1", "2"], labels);
+    }
+
+    #[test]
+    fn reset_layout_restores_initial_positions() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        let positions = |app: &GraphApp| {
+            (0..4)
+                .map(|node_index| {
+                    app.graph
+                        .node(NodeIndex::new(node_index))
+                        .unwrap()
+                        .location()
+                })
+                .collect::<Vec<_>>()
+        };
+        let initial_positions = positions(&app);
+        assert_eq!(Layout::Circular.positions(4), initial_positions);
+
+        let node = app.graph.node_mut(NodeIndex::new(2)).unwrap();
+        node.set_location(node.location() + Vec2::new(30., -10.));
+        assert_ne!(initial_positions, positions(&app));
+
+        app.reset_layout();
+        assert_eq!(initial_positions, positions(&app));
+    }
+
+    #[test]
+    fn hovering_finds_nodes_and_edges() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        // the node shapes only learn their positions when they are drawn
+        headless::render_frame(&mut app);
+        // without zoom and pan, screen positions are the same as canvas positions
+        let meta = Metadata::default();
+        let location = |node_index| {
+            app.graph
+                .node(NodeIndex::new(node_index))
+                .unwrap()
+                .location()
+        };
+
+        let near_node = location(2) + Vec2::new(1., 1.);
+        assert_eq!(
+            Some(Hovered::Node(NodeIndex::new(2))),
+            app.hovered(&meta, near_node)
+        );
+        assert_eq!(
+            Some("Node 2".to_string()),
+            app.hover_text(Hovered::Node(NodeIndex::new(2)))
+        );
+
+        let on_edge = location(1) + (location(2) - location(1)) / 2.;
+        assert_eq!(
+            Some(Hovered::Edge(EdgeIndex::new(1))),
+            app.hovered(&meta, on_edge)
+        );
+        assert_eq!(
+            Some("Edge 1 (1, 2), cut edge".to_string()),
+            app.hover_text(Hovered::Edge(EdgeIndex::new(1)))
+        );
+
+        assert_eq!(None, app.hovered(&meta, Pos2::new(1000., 1000.)));
+    }
+}
@@ -0,0 +1,45 @@
+use egui::Color32;
+
+/// Colors of the source and destination nodes and of the separator edges. Nodes that are
+/// interacted with, i.e. selected or dragged, use the lighter interacted colors. Nodes and edges
+/// that are none of these are drawn in the colors of the egui style.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorScheme {
+    pub source: Color32,
+    pub source_interacted: Color32,
+    pub destination: Color32,
+    pub destination_interacted: Color32,
+    pub separator: Color32,
+    /// Whether separator edges are also drawn thicker and dashed, so that they can be told apart
+    /// from the other edges without relying on their color
+    pub separator_dashed: bool,
+}
+
+impl ColorScheme {
+    pub(crate) fn source_color(&self, is_interacted: bool) -> Color32 {
+        match is_interacted {
+            true => self.source_interacted,
+            false => self.source,
+        }
+    }
+
+    pub(crate) fn destination_color(&self, is_interacted: bool) -> Color32 {
+        match is_interacted {
+            true => self.destination_interacted,
+            false => self.destination,
+        }
+    }
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self {
+            source: Color32::from_rgb(0x80, 0x80, 0xFF),
+            source_interacted: Color32::from_rgb(0xB0, 0xB0, 0xFF),
+            destination: Color32::from_rgb(0xFF, 0x80, 0x80),
+            destination_interacted: Color32::from_rgb(0xFF, 0xB0, 0xB0),
+            separator: Color32::from_rgb(0x90, 0xEE, 0x90),
+            separator_dashed: false,
+        }
+    }
+}
@@ -1,24 +1,58 @@
 use eframe::emath::{Pos2, Vec2};
-use eframe::epaint::Shape;
+use eframe::epaint::{CubicBezierShape, Shape};
 use egui::{Color32, Stroke};
 use egui_graphs::{DisplayEdge, DisplayNode, DrawContext, EdgeProps, Metadata, Node};
 use petgraph::stable_graph::IndexType;
 use petgraph::EdgeType;
 
+use crate::visualization::color::ColorScheme;
+
 // Based on DefaultEdgeShape
 
-trait SeparatorInfo {
+pub(crate) trait SeparatorInfo {
     fn get_is_separator(&self) -> bool;
+    fn get_color_scheme(&self) -> ColorScheme;
+    fn get_path_id(&self) -> Option<usize>;
+    fn get_capacity(&self) -> usize;
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct EdgeData {
     is_separator: bool,
+    color_scheme: ColorScheme,
+    /// Index of the augmenting path the edge is on, if it is highlighted as part of one
+    path_id: Option<usize>,
+    /// Number of edges of the original graph that the edge stands for
+    capacity: usize,
 }
 
 impl EdgeData {
     pub(crate) fn new(is_separator: bool) -> Self {
-        Self { is_separator }
+        Self {
+            is_separator,
+            color_scheme: ColorScheme::default(),
+            path_id: None,
+            capacity: 1,
+        }
+    }
+
+    pub(crate) fn set_is_separator(&mut self, is_separator: bool) {
+        self.is_separator = is_separator;
+    }
+
+    pub(crate) fn set_path_id(&mut self, path_id: Option<usize>) {
+        self.path_id = path_id;
+    }
+
+    pub(crate) fn with_color_scheme(self, color_scheme: ColorScheme) -> Self {
+        Self {
+            color_scheme,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_capacity(self, capacity: usize) -> Self {
+        Self { capacity, ..self }
     }
 }
 
@@ -26,9 +60,43 @@ impl SeparatorInfo for EdgeData {
     fn get_is_separator(&self) -> bool {
         self.is_separator
     }
+
+    fn get_color_scheme(&self) -> ColorScheme {
+        self.color_scheme
+    }
+
+    fn get_path_id(&self) -> Option<usize> {
+        self.path_id
+    }
+
+    fn get_capacity(&self) -> usize {
+        self.capacity
+    }
 }
 
-const SEPARATOR: Color32 = Color32::from_rgb(0x90, 0xEE, 0x90);
+/// Colors of highlighted augmenting paths, which are reused if there are more paths than colors.
+const PATH_COLORS: [Color32; 6] = [
+    Color32::from_rgb(0xFF, 0xC8, 0x57),
+    Color32::from_rgb(0x6F, 0xC8, 0xFF),
+    Color32::from_rgb(0xE0, 0x8A, 0xFF),
+    Color32::from_rgb(0xFF, 0x9E, 0x6F),
+    Color32::from_rgb(0x5F, 0xE0, 0xC8),
+    Color32::from_rgb(0xFF, 0x8A, 0xC0),
+];
+
+pub(crate) fn path_color(path_id: usize) -> Color32 {
+    PATH_COLORS[path_id % PATH_COLORS.len()]
+}
+
+const MIN_WIDTH: f32 = 2.;
+const MAX_WIDTH: f32 = 8.;
+const WIDTH_PER_CAPACITY: f32 = 0.75;
+
+/// Width of an edge with the given capacity, which grows with the capacity so that bottlenecks
+/// stand out, but stays between [`MIN_WIDTH`] and [`MAX_WIDTH`].
+fn width_for_capacity(capacity: usize) -> f32 {
+    (MIN_WIDTH + WIDTH_PER_CAPACITY * capacity.saturating_sub(1) as f32).clamp(MIN_WIDTH, MAX_WIDTH)
+}
 
 #[derive(Clone)]
 pub(crate) struct CustomEdgeShape {
@@ -39,24 +107,30 @@ pub(crate) struct CustomEdgeShape {
     width: f32,
     tip_size: f32,
     tip_angle: f32,
+    curve_size: f32,
+    loop_size: f32,
     is_separator: bool,
+    separator_color: Color32,
+    separator_dashed: bool,
+    path_id: Option<usize>,
 }
 
 impl<E: Clone + SeparatorInfo> From<EdgeProps<E>> for CustomEdgeShape {
     fn from(edge_props: EdgeProps<E>) -> Self {
-        assert_eq!(
-            0usize, edge_props.order,
-            "CustomEdgeShape only renders simple graphs (order 0)"
-        );
         Self {
             order: edge_props.order,
             selected: edge_props.selected,
             label_text: edge_props.label.to_string(),
 
-            width: 2.,
+            width: width_for_capacity(edge_props.payload.get_capacity()),
             tip_size: 12.5,
             tip_angle: std::f32::consts::TAU / 30.,
+            curve_size: 20.,
+            loop_size: 3.,
             is_separator: edge_props.payload.get_is_separator(),
+            separator_color: edge_props.payload.get_color_scheme().separator,
+            separator_dashed: edge_props.payload.get_color_scheme().separator_dashed,
+            path_id: edge_props.payload.get_path_id(),
         }
     }
 }
@@ -67,13 +141,14 @@ impl CustomEdgeShape {
         is_directed: bool,
         start: Pos2,
         end: Pos2,
-        line_points: &mut Vec<Pos2>,
+        line_points: &mut [Pos2],
     ) -> Vec<Pos2> {
         if !is_directed {
             return vec![];
         }
 
-        let tip_dir = (end - start).normalized();
+        let tip_dir = direction(start, end);
+        let last = line_points.len() - 1;
         let tip_angle = self.tip_angle;
         let tip_size = self.tip_size;
 
@@ -84,16 +159,50 @@ impl CustomEdgeShape {
         let tip_start_2 = end - arrow_tip_dir_2;
 
         // replace end of an edge with start of tip
-        *line_points.get_mut(1).unwrap() = end - tip_size * tip_dir;
+        *line_points.get_mut(last).unwrap() = end - tip_size * tip_dir;
 
         vec![end, tip_start_1, tip_start_2]
     }
 
+    /// Control points of the curve of a self-loop, which starts and ends on the upper half of the
+    /// node and rises above it. Loops of a higher order are drawn larger so that they don't
+    /// overlap.
+    fn loop_points(&self, node_center: Pos2, node_radius: f32) -> [Pos2; 4] {
+        let angle = std::f32::consts::FRAC_PI_4;
+        let y = node_center.y - node_radius * angle.sin();
+        let loop_start = Pos2::new(node_center.x + node_radius * angle.cos(), y);
+        let loop_end = Pos2::new(node_center.x - node_radius * angle.cos(), y);
+
+        let size = node_radius * (self.loop_size + self.order as f32);
+        [
+            loop_start,
+            Pos2::new(node_center.x + size, node_center.y - size),
+            Pos2::new(node_center.x - size, node_center.y - size),
+            loop_end,
+        ]
+    }
+
+    /// Control points of the curve of a parallel edge between `start` and `end`. As in
+    /// `egui_graphs`, the edge of order 0 is drawn straight, and the curve of every further
+    /// parallel edge bends further away from the straight line the higher its order is.
+    fn curve_points(&self, start: Pos2, end: Pos2) -> [Pos2; 4] {
+        let dir = direction(start, end);
+        let normal = Vec2::new(-dir.y, dir.x);
+        let center = start + (end - start) / 2.;
+        let control_center = center + normal * self.curve_size * self.order as f32;
+        [
+            start,
+            control_center - dir * self.curve_size,
+            control_center + dir * self.curve_size,
+            end,
+        ]
+    }
+
     fn scale_stroke(metadata: &Metadata, stroke: &mut Stroke) {
         stroke.width = metadata.canvas_to_screen_size(stroke.width);
     }
 
-    fn scale_points(metadata: &Metadata, points: &mut Vec<Pos2>) {
+    fn scale_points(metadata: &Metadata, points: &mut [Pos2]) {
         for i in 0..points.len() {
             *points.get_mut(i).unwrap() = metadata.canvas_to_screen_pos(points[i]);
         }
@@ -114,12 +223,13 @@ impl<
         end_node: &Node<N, E, Ty, Ix, D>,
         ctx: &DrawContext,
     ) -> Vec<Shape> {
-        // Note that we assume the graphs we're working with to be simple graphs
         let mut res = vec![];
 
-        let color = match self.is_separator {
-            true => SEPARATOR,
-            false => {
+        // cut edges stay highlighted on top of the paths
+        let color = match (self.is_separator, self.path_id) {
+            (true, _) => self.separator_color,
+            (false, Some(path_id)) => path_color(path_id),
+            (false, None) => {
                 let style = match self.selected {
                     true => ctx.ctx.style().visuals.widgets.active,
                     false => ctx.ctx.style().visuals.widgets.inactive,
@@ -128,23 +238,73 @@ impl<
             }
         };
 
-        let mut stroke = Stroke::new(self.width, color);
+        let is_dashed = self.is_separator && self.separator_dashed;
+        let width = match is_dashed {
+            true => 1.5 * self.width,
+            false => self.width,
+        };
+        let mut stroke = Stroke::new(width, color);
+
+        if start_node.id() == end_node.id() {
+            let mut loop_points = self.loop_points(start_node.location(), node_radius(start_node));
+            Self::scale_stroke(ctx.meta, &mut stroke);
+            Self::scale_points(ctx.meta, &mut loop_points);
+            res.push(
+                CubicBezierShape::from_points_stroke(
+                    loop_points,
+                    false,
+                    Color32::TRANSPARENT,
+                    stroke,
+                )
+                .into(),
+            );
+            return res;
+        }
 
-        let dir = (end_node.location() - start_node.location()).normalized();
+        let dir = direction(start_node.location(), end_node.location());
         let start = start_node.display().closest_boundary_point(dir);
         let end = end_node.display().closest_boundary_point(-dir);
 
-        let mut line_points = vec![start, end];
-        let mut tip_points = self.get_tip_points(ctx.is_directed, start, end, &mut line_points);
+        let mut line_points = match self.order {
+            0 => vec![start, end],
+            _ => self.curve_points(start, end).to_vec(),
+        };
+        // the tip points along the last part of the line, which is curved for parallel edges
+        let tip_start = line_points[line_points.len() - 2];
+        let mut tip_points = self.get_tip_points(ctx.is_directed, tip_start, end, &mut line_points);
 
         Self::scale_stroke(ctx.meta, &mut stroke);
         Self::scale_points(ctx.meta, &mut line_points);
         Self::scale_points(ctx.meta, &mut tip_points);
 
-        res.push(Shape::line_segment(
-            [line_points[0], line_points[1]],
-            stroke,
-        ));
+        let line_shape = match line_points[..] {
+            [line_start, line_end] => Shape::line_segment([line_start, line_end], stroke),
+            [curve_start, control_start, control_end, curve_end] => {
+                CubicBezierShape::from_points_stroke(
+                    [curve_start, control_start, control_end, curve_end],
+                    false,
+                    Color32::TRANSPARENT,
+                    stroke,
+                )
+                .into()
+            }
+            _ => unreachable!("An edge is either a line or a cubic curve"),
+        };
+        if is_dashed {
+            let polyline = match line_shape {
+                Shape::CubicBezier(curve) => curve.flatten(None),
+                _ => line_points,
+            };
+            let dash_length = ctx.meta.canvas_to_screen_size(DASH_LENGTH);
+            let gap_length = ctx.meta.canvas_to_screen_size(GAP_LENGTH);
+            res.extend(
+                dash_segments(&polyline, dash_length, gap_length)
+                    .into_iter()
+                    .map(|segment| Shape::line_segment(segment, stroke)),
+            );
+        } else {
+            res.push(line_shape);
+        }
 
         if ctx.is_directed {
             res.push(Shape::convex_polygon(
@@ -163,6 +323,8 @@ impl<
         self.order = state.order;
         self.selected = state.selected;
         self.label_text = state.label.to_string();
+        self.is_separator = state.payload.get_is_separator();
+        self.path_id = state.payload.get_path_id();
     }
 
     fn is_inside(
@@ -171,14 +333,86 @@ impl<
         end: &Node<N, E, Ty, Ix, D>,
         pos: Pos2,
     ) -> bool {
+        if start.id() == end.id() {
+            let loop_points = self.loop_points(start.location(), node_radius(start));
+            return is_on_curve(loop_points, self.width, pos);
+        }
+
         let pos_start = start.location();
         let pos_end = end.location();
+        if self.order > 0 {
+            return is_on_curve(self.curve_points(pos_start, pos_end), self.width, pos);
+        }
 
         let distance = distance_segment_to_point(pos_start, pos_end, pos);
         distance <= self.width
     }
 }
 
+/// Length of the dashes of dashed separator edges and of the gaps between them.
+const DASH_LENGTH: f32 = 6.;
+const GAP_LENGTH: f32 = 4.;
+
+/// Splits the line through the given points into dashes of the given length, which are separated
+/// by gaps of the given length. The last dash may be shorter.
+fn dash_segments(points: &[Pos2], dash_length: f32, gap_length: f32) -> Vec<[Pos2; 2]> {
+    let mut segments = vec![];
+    let mut is_dash = true;
+    // how much of the current dash or gap is left
+    let mut remaining = dash_length;
+    for part in points.windows(2) {
+        let (mut from, to) = (part[0], part[1]);
+        let dir = direction(from, to);
+        let mut left = from.distance(to);
+        while left > 0. {
+            let step = remaining.min(left);
+            let next = from + dir * step;
+            if is_dash {
+                segments.push([from, next]);
+            }
+            from = next;
+            left -= step;
+            remaining -= step;
+            if remaining <= 0. {
+                is_dash = !is_dash;
+                remaining = if is_dash { dash_length } else { gap_length };
+            }
+        }
+    }
+    segments
+}
+
+/// Unit vector pointing from `start` to `end`. Nodes that are dragged on top of each other have no
+/// direction between them, so the edge is drawn horizontally instead.
+fn direction(start: Pos2, end: Pos2) -> Vec2 {
+    let dir = end - start;
+    if dir == Vec2::ZERO {
+        Vec2::X
+    } else {
+        dir.normalized()
+    }
+}
+
+/// Whether the point lies within `width` of the cubic curve with the given control points.
+fn is_on_curve(points: [Pos2; 4], width: f32, point: Pos2) -> bool {
+    CubicBezierShape::from_points_stroke(points, false, Color32::TRANSPARENT, Stroke::NONE)
+        .flatten(None)
+        .into_iter()
+        .any(|curve_point| curve_point.distance(point) <= width)
+}
+
+/// Distance from the center of the node to its boundary.
+fn node_radius<N, E, Ty, Ix, D>(node: &Node<N, E, Ty, Ix, D>) -> f32
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+    D: DisplayNode<N, E, Ty, Ix>,
+{
+    (node.display().closest_boundary_point(Vec2::X) - node.location()).length()
+}
+
 fn distance_segment_to_point(a: Pos2, b: Pos2, point: Pos2) -> f32 {
     let ac = point - a;
     let ab = b - a;
@@ -214,3 +448,143 @@ fn rotate_vector(vec: Vec2, angle: f32) -> Vec2 {
     let sin = angle.sin();
     Vec2::new(cos * vec.x - sin * vec.y, sin * vec.x + cos * vec.y)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use egui::Shape;
+    use petgraph::graph::UnGraph;
+
+    use crate::cuts::{augmenting_paths, Cut, IndexMapping};
+    use crate::visualization::animation::Progress;
+    use crate::visualization::app::GraphApp;
+    use crate::visualization::color::ColorScheme;
+    use crate::visualization::edge::path_color;
+    use crate::visualization::headless;
+
+    #[test]
+    fn self_loop_is_drawn_as_curve() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 1)]);
+        let cut = Cut::new(vec![0], vec![1], vec![0]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+
+        let shapes = headless::render_frame(&mut app);
+        let curves = shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::CubicBezier(curve) => Some(curve),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(1, headless::line_segment_strokes(&shapes).len());
+        assert_eq!(1, curves.len());
+        assert!(curves[0]
+            .points
+            .iter()
+            .all(|point| point.x.is_finite() && point.y.is_finite()));
+        assert_ne!(curves[0].points[0], curves[0].points[3]);
+    }
+
+    #[test]
+    fn parallel_edges_are_drawn_as_distinct_curves() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (0, 1), (0, 1)]);
+        let cut = Cut::new(vec![0], vec![1], vec![0, 1, 2]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+
+        let shapes = headless::render_frame(&mut app);
+        let curves = shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::CubicBezier(curve) => Some(curve.points),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        // the first of the parallel edges is drawn straight
+        assert_eq!(1, headless::line_segment_strokes(&shapes).len());
+        assert_eq!(2, curves.len());
+        assert_ne!(curves[0], curves[1]);
+    }
+
+    #[test]
+    fn path_edges_get_color_of_path() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 3), (0, 2), (2, 3)]);
+        let cut = Cut::new(vec![0, 1, 2], vec![3], vec![1, 3]);
+        let paths = augmenting_paths(&graph, 0, 3, 2).unwrap();
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        app.highlight_flow(&paths, Progress::Done);
+
+        let shapes = headless::render_frame(&mut app);
+        let colors = headless::line_segment_strokes(&shapes)
+            .into_iter()
+            .map(|stroke| stroke.color)
+            .collect::<Vec<_>>();
+
+        // the last edge of each path is a cut edge, which keeps its color
+        let count = |color| colors.iter().filter(|&&other| other == color).count();
+        assert_eq!(1, count(path_color(0)));
+        assert_eq!(1, count(path_color(1)));
+        assert_eq!(2, count(ColorScheme::default().separator));
+    }
+
+    #[test]
+    fn dashed_separator_is_split_into_segments() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let cut = Cut::new(vec![0], vec![1, 2], vec![0]);
+        let color_scheme = ColorScheme {
+            separator_dashed: true,
+            ..ColorScheme::default()
+        };
+        let mut app = GraphApp::new(graph, cut, None, color_scheme);
+
+        let shapes = headless::render_frame(&mut app);
+        let separator_lengths = shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::LineSegment { points, stroke } if stroke.color == color_scheme.separator => {
+                    Some(points[0].distance(points[1]))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let other_lengths = shapes
+            .iter()
+            .filter_map(|shape| match shape {
+                Shape::LineSegment { points, stroke } if stroke.color != color_scheme.separator => {
+                    Some(points[0].distance(points[1]))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        assert!(separator_lengths.len() > 1);
+        assert_eq!(1, other_lengths.len());
+        assert!(separator_lengths
+            .iter()
+            .all(|&length| length < other_lengths[0]));
+    }
+
+    #[test]
+    fn edges_with_more_capacity_are_wider() {
+        let contracted_graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2)]);
+        let cut = Cut::new(vec![0, 1], vec![2], vec![1]);
+        let index_mapping = IndexMapping::from(
+            HashMap::from([(0, vec![0]), (1, vec![1]), (2, vec![2])]),
+            HashMap::from([(0, (0..10).collect()), (1, vec![10])]),
+        );
+        let mut app = GraphApp::new(
+            contracted_graph,
+            cut,
+            Some(index_mapping),
+            ColorScheme::default(),
+        );
+
+        let shapes = headless::render_frame(&mut app);
+        let strokes = headless::line_segment_strokes(&shapes);
+
+        assert_eq!(2, strokes.len());
+        assert!(strokes[0].width > strokes[1].width);
+    }
+}
@@ -0,0 +1,44 @@
+use egui::{Context, Pos2, RawInput, Rect, Shape, Stroke, Vec2};
+
+use crate::visualization::app::GraphApp;
+
+/// Runs a single frame of the app without a window and returns the shapes emitted by the panel
+/// contents, with nested shapes flattened. The frames and separator lines of the panels are
+/// clipped to the whole screen and skipped.
+pub(crate) fn render_frame(app: &mut GraphApp) -> Vec<Shape> {
+    let ctx = Context::default();
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(800., 600.));
+    let input = RawInput {
+        screen_rect: Some(screen_rect),
+        ..Default::default()
+    };
+    let output = ctx.run(input, |ctx| app.ui(ctx));
+
+    let mut shapes = vec![];
+    for clipped_shape in output.shapes {
+        if clipped_shape.clip_rect != screen_rect {
+            flatten(clipped_shape.shape, &mut shapes);
+        }
+    }
+    shapes
+}
+
+fn flatten(shape: Shape, shapes: &mut Vec<Shape>) {
+    match shape {
+        Shape::Vec(nested) => nested
+            .into_iter()
+            .for_each(|nested_shape| flatten(nested_shape, shapes)),
+        shape => shapes.push(shape),
+    }
+}
+
+/// Strokes of all line segments among the given shapes.
+pub(crate) fn line_segment_strokes(shapes: &[Shape]) -> Vec<Stroke> {
+    shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            Shape::LineSegment { stroke, .. } => Some(*stroke),
+            _ => None,
+        })
+        .collect()
+}
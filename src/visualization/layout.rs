@@ -0,0 +1,83 @@
+use std::f32::consts::TAU;
+use std::fmt::{Display, Formatter};
+
+use egui::Pos2;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Size of the area that nodes are placed in, same as the default of egui_graphs.
+const SPAWN_SIZE: f32 = 250.;
+
+/// Determines where the nodes of the visualized graph are placed. Every layout is deterministic,
+/// so that the same graph is always laid out the same way.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum Layout {
+    /// Nodes are placed evenly on a circle in the order of their indices
+    #[default]
+    Circular,
+    /// Nodes are placed uniformly at random using the given seed
+    Random { seed: u64 },
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Layout::Circular => write!(f, "Circular"),
+            Layout::Random { .. } => write!(f, "Random"),
+        }
+    }
+}
+
+impl Layout {
+    /// Positions of the nodes `0..node_count`.
+    pub(crate) fn positions(&self, node_count: usize) -> Vec<Pos2> {
+        match self {
+            Layout::Circular => {
+                let radius = SPAWN_SIZE / 2.;
+                (0..node_count)
+                    .map(|node_index| {
+                        let angle = TAU * node_index as f32 / node_count as f32;
+                        Pos2::new(radius * angle.cos(), radius * angle.sin())
+                    })
+                    .collect()
+            }
+            Layout::Random { seed } => {
+                let mut rng = StdRng::seed_from_u64(*seed);
+                (0..node_count)
+                    .map(|_| {
+                        Pos2::new(
+                            rng.gen_range(0. ..SPAWN_SIZE),
+                            rng.gen_range(0. ..SPAWN_SIZE),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::visualization::layout::Layout;
+
+    #[test]
+    fn layouts_are_deterministic() {
+        for layout in [Layout::Circular, Layout::Random { seed: 7 }] {
+            assert_eq!(layout.positions(5), layout.positions(5));
+        }
+        assert_ne!(
+            Layout::Random { seed: 7 }.positions(5),
+            Layout::Random { seed: 8 }.positions(5)
+        );
+    }
+
+    #[test]
+    fn circular_layout_places_nodes_apart() {
+        let positions = Layout::Circular.positions(4);
+        for (i, first) in positions.iter().enumerate() {
+            for second in &positions[i + 1..] {
+                assert!(first.distance(*second) > 1.);
+            }
+        }
+    }
+}
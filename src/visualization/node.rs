@@ -5,71 +5,93 @@ use egui_graphs::{DisplayNode, DrawContext, NodeProps};
 use petgraph::stable_graph::IndexType;
 use petgraph::EdgeType;
 
-trait SourceDestinationInfo {
+use crate::visualization::color::ColorScheme;
+
+pub(crate) trait SourceDestinationInfo {
     fn get_node_type(&self) -> NodeType;
+    fn get_color_scheme(&self) -> ColorScheme;
+    fn get_degree(&self) -> usize;
 }
 
 #[derive(Clone, Debug)]
-enum NodeType {
-    SOURCE,
-    DESTINATION,
-    OTHER,
+pub(crate) enum NodeType {
+    Source,
+    Destination,
+    Other,
 }
 
 #[derive(Clone, Debug)]
 pub(crate) struct NodeData {
     node_type: NodeType,
+    color_scheme: ColorScheme,
+    degree: usize,
 }
 
 impl NodeData {
     pub(crate) fn new() -> Self {
         Self {
-            node_type: NodeType::OTHER,
+            node_type: NodeType::Other,
+            color_scheme: ColorScheme::default(),
+            degree: 0,
         }
     }
 
     pub(crate) fn new_source() -> Self {
         Self {
-            node_type: NodeType::SOURCE,
+            node_type: NodeType::Source,
+            color_scheme: ColorScheme::default(),
+            degree: 0,
         }
     }
 
     pub(crate) fn new_destination() -> Self {
         Self {
-            node_type: NodeType::DESTINATION,
+            node_type: NodeType::Destination,
+            color_scheme: ColorScheme::default(),
+            degree: 0,
         }
     }
+
+    pub(crate) fn with_color_scheme(self, color_scheme: ColorScheme) -> Self {
+        Self {
+            color_scheme,
+            ..self
+        }
+    }
+
+    pub(crate) fn with_degree(self, degree: usize) -> Self {
+        Self { degree, ..self }
+    }
+
+    pub(crate) fn set_node_type(&mut self, node_type: NodeType) {
+        self.node_type = node_type;
+    }
 }
 
 impl SourceDestinationInfo for NodeData {
     fn get_node_type(&self) -> NodeType {
         self.node_type.clone()
     }
-}
-
-struct SourceDestinationColor;
 
-impl SourceDestinationColor {
-    const SOURCE: Color32 = Color32::from_rgb(0x80, 0x80, 0xFF);
-    const SOURCE_INTERACTED: Color32 = Color32::from_rgb(0xB0, 0xB0, 0xFF);
-    const DESTINATION: Color32 = Color32::from_rgb(0xFF, 0x80, 0x80);
-    const DESTINATION_INTERACTED: Color32 = Color32::from_rgb(0xFF, 0xB0, 0xB0);
-
-    fn get_source_color(is_interacted: bool) -> Color32 {
-        match is_interacted {
-            true => Self::SOURCE_INTERACTED,
-            false => Self::SOURCE,
-        }
+    fn get_color_scheme(&self) -> ColorScheme {
+        self.color_scheme
     }
 
-    fn get_destination_color(is_interacted: bool) -> Color32 {
-        match is_interacted {
-            true => Self::DESTINATION_INTERACTED,
-            false => Self::DESTINATION,
-        }
+    fn get_degree(&self) -> usize {
+        self.degree
     }
 }
 
+const MIN_RADIUS: f32 = 5.;
+const MAX_RADIUS: f32 = 15.;
+const RADIUS_PER_DEGREE: f32 = 1.5;
+
+/// Radius of a node with the given degree, which grows with the degree so that hubs stand out,
+/// but stays between [`MIN_RADIUS`] and [`MAX_RADIUS`].
+fn radius_for_degree(degree: usize) -> f32 {
+    (MIN_RADIUS + RADIUS_PER_DEGREE * degree.saturating_sub(1) as f32).clamp(MIN_RADIUS, MAX_RADIUS)
+}
+
 #[derive(Clone)]
 pub(crate) struct CustomNodeShape {
     pos: Pos2,
@@ -79,6 +101,7 @@ pub(crate) struct CustomNodeShape {
 
     radius: f32,
     node_type: NodeType,
+    color_scheme: ColorScheme,
 }
 
 impl<N: Clone + SourceDestinationInfo> From<NodeProps<N>> for CustomNodeShape {
@@ -88,8 +111,9 @@ impl<N: Clone + SourceDestinationInfo> From<NodeProps<N>> for CustomNodeShape {
             label_text: node_props.label.to_string(),
             selected: node_props.selected,
             dragged: node_props.dragged,
-            radius: 5.0,
+            radius: radius_for_degree(node_props.payload.get_degree()),
             node_type: node_props.payload.get_node_type(),
+            color_scheme: node_props.payload.get_color_scheme(),
         }
     }
 }
@@ -107,9 +131,9 @@ impl<N: Clone + SourceDestinationInfo, E: Clone, Ty: EdgeType, Ix: IndexType>
         let is_interacted = self.selected || self.dragged;
 
         let color = match self.node_type {
-            NodeType::SOURCE => SourceDestinationColor::get_source_color(is_interacted),
-            NodeType::DESTINATION => SourceDestinationColor::get_destination_color(is_interacted),
-            NodeType::OTHER => {
+            NodeType::Source => self.color_scheme.source_color(is_interacted),
+            NodeType::Destination => self.color_scheme.destination_color(is_interacted),
+            NodeType::Other => {
                 let style = match is_interacted {
                     true => ctx.ctx.style().visuals.widgets.active,
                     false => ctx.ctx.style().visuals.widgets.inactive,
@@ -128,13 +152,13 @@ impl<N: Clone + SourceDestinationInfo, E: Clone, Ty: EdgeType, Ix: IndexType>
         };
         res.push(circle_shape.into());
 
-        let black = Color32::BLACK;
+        let text_color = label_color(color);
 
         let galley = ctx.ctx.fonts(|f| {
             f.layout_no_wrap(
                 self.label_text.clone(),
                 FontId::new(circle_radius, FontFamily::Monospace),
-                black,
+                text_color,
             )
         });
 
@@ -144,7 +168,7 @@ impl<N: Clone + SourceDestinationInfo, E: Clone, Ty: EdgeType, Ix: IndexType>
             circle_center.y - galley.size().y / 2.,
         );
 
-        let label_shape = TextShape::new(label_pos, galley, black);
+        let label_shape = TextShape::new(label_pos, galley, text_color);
         res.push(label_shape.into());
 
         res
@@ -155,6 +179,7 @@ impl<N: Clone + SourceDestinationInfo, E: Clone, Ty: EdgeType, Ix: IndexType>
         self.label_text = state.label.to_string();
         self.selected = state.selected;
         self.dragged = state.dragged;
+        self.node_type = state.payload.get_node_type();
     }
 
     fn is_inside(&self, pos: Pos2) -> bool {
@@ -162,6 +187,16 @@ impl<N: Clone + SourceDestinationInfo, E: Clone, Ty: EdgeType, Ix: IndexType>
     }
 }
 
+/// Color of the label of a node with the given fill, which is black on light fills and white on
+/// dark ones, such as the neutral fill of the light theme.
+fn label_color(fill: Color32) -> Color32 {
+    let luminance = 0.299 * fill.r() as f32 + 0.587 * fill.g() as f32 + 0.114 * fill.b() as f32;
+    match luminance > 128. {
+        true => Color32::BLACK,
+        false => Color32::WHITE,
+    }
+}
+
 fn closest_point_on_circle(center: Pos2, radius: f32, dir: Vec2) -> Pos2 {
     center + dir.normalized() * radius
 }
@@ -170,3 +205,29 @@ fn is_inside_circle(center: Pos2, radius: f32, pos: Pos2) -> bool {
     let dir = pos - center;
     dir.length() <= radius
 }
+
+#[cfg(test)]
+mod tests {
+    use egui::{Color32, Visuals};
+
+    use crate::visualization::color::ColorScheme;
+    use crate::visualization::node::{label_color, radius_for_degree, MAX_RADIUS, MIN_RADIUS};
+
+    #[test]
+    fn radius_grows_with_degree() {
+        assert!(radius_for_degree(4) > radius_for_degree(1));
+        assert_eq!(MIN_RADIUS, radius_for_degree(0));
+        assert_eq!(MIN_RADIUS, radius_for_degree(1));
+        assert_eq!(MAX_RADIUS, radius_for_degree(100));
+    }
+
+    #[test]
+    fn labels_contrast_with_fill() {
+        let dark_neutral = Visuals::dark().widgets.inactive.fg_stroke.color;
+        let light_neutral = Visuals::light().widgets.inactive.fg_stroke.color;
+
+        assert_eq!(Color32::BLACK, label_color(dark_neutral));
+        assert_eq!(Color32::WHITE, label_color(light_neutral));
+        assert_eq!(Color32::BLACK, label_color(ColorScheme::default().source));
+    }
+}
@@ -0,0 +1,68 @@
+/// Options of the window the graph is drawn in and of what is drawn. The title and the size are
+/// only used by the native build, where the default size is the default window size of winit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VizOptions {
+    pub title: String,
+    /// Width and height of the window when it is opened
+    pub initial_size: (f32, f32),
+    /// Whether the dark or the light theme of egui is used
+    pub dark_mode: bool,
+    /// Whether the edges are labeled with their indices and the number of cuts is shown, which
+    /// helps to compare the visualization with the output of the algorithms
+    pub debug_overlay: bool,
+}
+
+impl VizOptions {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn native_options(&self) -> eframe::NativeOptions {
+        let (width, height) = self.initial_size;
+        eframe::NativeOptions {
+            viewport: egui::ViewportBuilder::default()
+                .with_title(&self.title)
+                .with_inner_size([width, height]),
+            ..eframe::NativeOptions::default()
+        }
+    }
+}
+
+impl Default for VizOptions {
+    fn default() -> Self {
+        Self {
+            title: "Important Separator Project".to_string(),
+            initial_size: (800., 600.),
+            dark_mode: true,
+            debug_overlay: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Vec2;
+
+    use crate::visualization::options::VizOptions;
+
+    #[test]
+    fn default_options_keep_title() {
+        let options = VizOptions::default();
+        assert_eq!("Important Separator Project", options.title);
+        assert!(options.dark_mode);
+        assert!(!options.debug_overlay);
+    }
+
+    #[test]
+    fn custom_title_and_size_are_applied() {
+        let options = VizOptions {
+            title: "Cuts".to_string(),
+            initial_size: (1024., 768.),
+            ..VizOptions::default()
+        };
+
+        let native_options = options.native_options();
+        assert_eq!(Some("Cuts".to_string()), native_options.viewport.title);
+        assert_eq!(
+            Some(Vec2::new(1024., 768.)),
+            native_options.viewport.inner_size
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use egui::Pos2;
+use petgraph::stable_graph::NodeIndex;
+use serde::{Deserialize, Serialize};
+
+use crate::cuts::Cut;
+use crate::visualization::app::{generate_graph, GraphApp};
+use crate::visualization::edge::SeparatorInfo;
+use crate::visualization::node::{NodeType, SourceDestinationInfo};
+
+/// Everything needed to restore the visualization of a graph: its structure, the node positions
+/// and labels, and the cut that is highlighted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PersistedState {
+    pub(crate) node_count: usize,
+    pub(crate) edges: Vec<(usize, usize)>,
+    pub(crate) positions: Vec<(f32, f32)>,
+    pub(crate) labels: Vec<String>,
+    pub(crate) source_set: Vec<usize>,
+    pub(crate) destination_set: Vec<usize>,
+    pub(crate) cut_edge_set: Vec<usize>,
+}
+
+impl PersistedState {
+    pub(crate) fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub(crate) fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+}
+
+impl GraphApp {
+    pub(crate) fn persisted_state(&self) -> PersistedState {
+        let mut state = PersistedState {
+            node_count: self.graph.node_count(),
+            edges: Vec::with_capacity(self.graph.edge_count()),
+            positions: Vec::with_capacity(self.graph.node_count()),
+            labels: Vec::with_capacity(self.graph.node_count()),
+            source_set: vec![],
+            destination_set: vec![],
+            cut_edge_set: vec![],
+        };
+
+        // Nodes and edges are never removed from the visualized graph, so its indices are the
+        // same as the ones of the graph it was generated from
+        for (node_index, node) in self.graph.nodes_iter() {
+            let location = node.location();
+            state.positions.push((location.x, location.y));
+            state.labels.push(node.label());
+            match node.payload().get_node_type() {
+                NodeType::Source => state.source_set.push(node_index.index()),
+                NodeType::Destination => state.destination_set.push(node_index.index()),
+                NodeType::Other => {}
+            }
+        }
+        for (edge_index, edge) in self.graph.edges_iter() {
+            let (source, target) = self
+                .graph
+                .edge_endpoints(edge_index)
+                .expect("Edge should have endpoints");
+            state.edges.push((source.index(), target.index()));
+            if edge.payload().get_is_separator() {
+                state.cut_edge_set.push(edge_index.index());
+            }
+        }
+        state
+    }
+
+    /// Replaces the visualized graph with the persisted one. Fails without changing the graph if
+    /// an edge of the persisted graph has an endpoint that does not exist.
+    pub(crate) fn restore(&mut self, state: PersistedState) -> io::Result<()> {
+        if let Some((source, target)) = state
+            .edges
+            .iter()
+            .find(|(source, target)| *source >= state.node_count || *target >= state.node_count)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Edge ({source}, {target}) has an endpoint outside of the graph"),
+            ));
+        }
+
+        let mut input_graph = petgraph::Graph::with_capacity(state.node_count, state.edges.len());
+        (0..state.node_count).for_each(|_| {
+            input_graph.add_node(());
+        });
+        for (source, target) in state.edges {
+            input_graph.add_edge(NodeIndex::new(source), NodeIndex::new(target), ());
+        }
+        let cut = Cut::new(state.source_set, state.destination_set, state.cut_edge_set);
+
+        self.graph = generate_graph(
+            &input_graph,
+            cut.clone(),
+            None,
+            self.layout,
+            self.color_scheme,
+        );
+        self.cuts = vec![cut];
+        self.cut_index = 0;
+        self.restore_camera = true;
+        let nodes = state.positions.into_iter().zip(state.labels);
+        for (node_index, ((x, y), label)) in nodes.enumerate() {
+            if let Some(node) = self.graph.node_mut(NodeIndex::new(node_index)) {
+                node.set_location(Pos2::new(x, y));
+                node.set_label(label);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = self.persisted_state().to_json()?;
+        fs::write(path, json)
+    }
+
+    pub(crate) fn load_from_file(&mut self, path: impl AsRef<Path>) -> io::Result<()> {
+        let json = fs::read_to_string(path)?;
+        let state = PersistedState::from_json(&json)?;
+        self.restore(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::graph::UnGraph;
+
+    use crate::cuts::Cut;
+    use crate::visualization::app::{Camera, GraphApp};
+    use crate::visualization::color::ColorScheme;
+    use crate::visualization::headless;
+    use crate::visualization::persistence::PersistedState;
+
+    #[test]
+    fn persisted_state_round_trip() {
+        let state = PersistedState {
+            node_count: 4,
+            edges: vec![(0, 1), (1, 2), (1, 3)],
+            positions: vec![(0., 0.), (10.5, 20.), (-3., 4.25), (100., 0.)],
+            labels: vec!["0".into(), "1".into(), "{2,5}".into(), "3".into()],
+            source_set: vec![0, 1],
+            destination_set: vec![2, 3],
+            cut_edge_set: vec![1, 2],
+        };
+
+        let json = state.to_json().unwrap();
+        assert_eq!(state, PersistedState::from_json(&json).unwrap());
+    }
+
+    #[test]
+    fn restoring_state_rebuilds_graph() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        let state = app.persisted_state();
+
+        let other_graph = UnGraph::<(), ()>::from_edges([(0, 1)]);
+        let other_cut = Cut::new(vec![0], vec![1], vec![0]);
+        let mut restored_app = GraphApp::new(other_graph, other_cut, None, ColorScheme::default());
+        restored_app.restore(state.clone()).unwrap();
+
+        assert_eq!(state, restored_app.persisted_state());
+    }
+
+    #[test]
+    fn restoring_state_keeps_camera() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1), (1, 2), (1, 3)]);
+        let cut = Cut::new(vec![0, 1], vec![2, 3], vec![1, 2]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        headless::render_frame(&mut app);
+        let camera = Camera {
+            zoom: 2.5,
+            ..app.camera.unwrap()
+        };
+        app.camera = Some(camera);
+
+        app.restore(app.persisted_state()).unwrap();
+        headless::render_frame(&mut app);
+
+        assert_eq!(Some(camera), app.camera);
+    }
+
+    #[test]
+    fn restoring_state_with_dangling_edge_fails() {
+        let graph = UnGraph::<(), ()>::from_edges([(0, 1)]);
+        let cut = Cut::new(vec![0], vec![1], vec![0]);
+        let mut app = GraphApp::new(graph, cut, None, ColorScheme::default());
+        let mut state = app.persisted_state();
+        state.edges.push((1, 2));
+
+        assert!(app.restore(state).is_err());
+        assert_eq!(2, app.persisted_state().node_count);
+    }
+}
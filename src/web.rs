@@ -0,0 +1,32 @@
+use petgraph::graph::UnGraph;
+use wasm_bindgen::prelude::*;
+
+use crate::cuts::Cut;
+use crate::visualization::{self, ColorScheme, VizOptions};
+
+/// Entry point of the web build. Draws the graph given as a flat list of edge endpoints, i.e.
+/// `[s_0, t_0, s_1, t_1, ...]`, on the canvas with the given id and highlights the given cut. Every
+/// vertex that is not in the source set is drawn as part of the destination set.
+#[wasm_bindgen]
+pub async fn start(
+    canvas_id: String,
+    edges: Vec<u32>,
+    source_set: Vec<usize>,
+    cut_edge_set: Vec<usize>,
+) -> Result<(), JsValue> {
+    let graph = UnGraph::<(), ()>::from_edges(edges.chunks_exact(2).map(|pair| (pair[0], pair[1])));
+    let destination_set = (0..graph.node_count())
+        .filter(|node| !source_set.contains(node))
+        .collect();
+    let cut = Cut::new(source_set, destination_set, cut_edge_set);
+
+    visualization::start_web(
+        &canvas_id,
+        graph,
+        cut,
+        None,
+        ColorScheme::default(),
+        VizOptions::default(),
+    )
+    .await
+}